@@ -1,7 +1,6 @@
 use zeroentropy_community::{Client, MetadataValue};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::time::sleep;
 
 /// Simplified example of downloading and searching arXiv papers
 /// 
@@ -15,37 +14,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create collection
     println!("\n=== Creating Collection ===");
-    match client.collections().add(collection).await {
-        Ok(_) => println!("Collection '{}' created", collection),
-        Err(zeroentropy_community::Error::Conflict(_)) => {
-            println!("Collection '{}' already exists", collection)
-        }
-        Err(e) => return Err(e.into()),
+    if client.collections().ensure(collection).await? {
+        println!("Collection '{}' created", collection);
+    } else {
+        println!("Collection '{}' already exists", collection);
     }
 
     // Download a classic paper: "Attention Is All You Need"
     let arxiv_id = "1706.03762";
     let pdf_url = format!("https://arxiv.org/pdf/{}.pdf", arxiv_id);
     
-    println!("\n=== Downloading Paper ===");
-    println!("Paper: Attention Is All You Need ({})", arxiv_id);
-    println!("Downloading from: {}", pdf_url);
-
-    let http_client = reqwest::Client::new();
-    let pdf_bytes = http_client
-        .get(&pdf_url)
-        .send()
-        .await?
-        .bytes()
-        .await?;
-    
-    // Save PDF temporarily
-    let pdf_path = format!("/tmp/arxiv_{}.pdf", arxiv_id);
-    std::fs::write(&pdf_path, pdf_bytes)?;
-    println!("Downloaded to: {}", pdf_path);
-
     // Index the paper with metadata
     println!("\n=== Indexing Paper ===");
+    println!("Paper: Attention Is All You Need ({})", arxiv_id);
+    println!("Downloading from: {}", pdf_url);
     let mut metadata = HashMap::new();
     metadata.insert(
         "title".to_string(),
@@ -64,21 +46,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         MetadataValue::String("2017-06-12".to_string()),
     );
 
+    let document_path = format!("arxiv_{}.pdf", arxiv_id);
     match client
         .documents()
-        .add_pdf_file(
-            collection,
-            &format!("arxiv_{}.pdf", arxiv_id),
-            &pdf_path,
-            Some(metadata),
-        )
+        .add_url(collection, &document_path, &pdf_url, Some(metadata))
         .await
     {
         Ok(_) => {
-            println!("Paper indexed successfully");
-            // Wait a moment for the document to be fully processed
+            println!("Paper uploaded");
             println!("Waiting for document processing...");
-            sleep(Duration::from_secs(3)).await;
+            client
+                .documents()
+                .wait_until_indexed(
+                    collection,
+                    &document_path,
+                    Duration::from_secs(120),
+                    Duration::from_secs(1),
+                )
+                .await?;
+            println!("Paper indexed successfully");
         }
         Err(zeroentropy_community::Error::Conflict(_)) => {
             println!("Paper already indexed, using existing version");
@@ -112,9 +98,5 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("{}", "─".repeat(80));
     }
 
-    // Clean up
-    std::fs::remove_file(&pdf_path)?;
-    println!("\nCleaned up temporary PDF");
-
     Ok(())
 }
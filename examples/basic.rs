@@ -8,10 +8,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create a collection
     println!("Creating collection...");
-    match client.collections().add("rust_example").await {
-        Ok(response) => println!("{}", response.message),
-        Err(zeroentropy_community::Error::Conflict(_)) => println!("Collection already exists"),
-        Err(e) => return Err(e.into()),
+    if client.collections().ensure("rust_example").await? {
+        println!("Collection created");
+    } else {
+        println!("Collection already exists");
     }
 
     // Add some text documents
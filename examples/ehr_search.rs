@@ -1,7 +1,6 @@
-use zeroentropy_community::{Client, MetadataValue};
+use zeroentropy_community::{Client, MetadataValue, F};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::time::sleep;
 
 /// Example demonstrating semantic search over Electronic Health Records (EHR)
 /// 
@@ -30,12 +29,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create collection
     println!("Creating collection '{}'...", collection);
-    match client.collections().add(collection).await {
-        Ok(_) => println!("✓ Collection created"),
-        Err(zeroentropy_community::Error::Conflict(_)) => {
-            println!("✓ Collection already exists")
-        }
-        Err(e) => return Err(e.into()),
+    if client.collections().ensure(collection).await? {
+        println!("✓ Collection created");
+    } else {
+        println!("✓ Collection already exists");
     }
 
     // Check if we need to index documents
@@ -64,7 +61,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // CSV columns: description, medical_specialty, sample_name, transcription, keywords
             let description = record.get(0).unwrap_or("");
             let specialty = record.get(1).unwrap_or("");
-            let sample_name = record.get(2).unwrap_or("");
+            let _sample_name = record.get(2).unwrap_or("");
             let transcription = record.get(3).unwrap_or("");
             let keywords = record.get(4).unwrap_or("");
             
@@ -112,7 +109,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         
         println!("\n✓ Indexed {} medical transcriptions", count);
         println!("Waiting for indexing to complete...");
-        sleep(Duration::from_secs(5)).await;
+        let report = client
+            .collections()
+            .wait_until_indexed_with_progress(collection, Duration::from_secs(600), |report| {
+                print!("\r{}/{} indexed", report.indexed, report.total());
+                let _ = std::io::Write::flush(&mut std::io::stdout());
+            })
+            .await?;
+        println!();
+        if report.failed() > 0 {
+            eprintln!("Warning: {} documents failed to index", report.failed());
+        }
     } else {
         println!("✓ Collection already contains documents");
     }
@@ -195,9 +202,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Demonstrate metadata filtering
     println!("Query: 'patient assessment' filtered to Cardiology specialty");
     
-    let filter = serde_json::json!({
-        "specialty": { "$eq": "Cardiovascular / Pulmonary" }
-    });
+    let filter = F::field("specialty").eq("Cardiovascular / Pulmonary").build();
     
     let results = client
         .queries()
@@ -205,7 +210,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             collection,
             "patient assessment",
             3,
-            Some(filter.as_object().unwrap().clone()),
+            Some(filter),
             Some(true),
             None,
             None,
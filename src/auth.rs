@@ -0,0 +1,48 @@
+//! API key resolution
+//!
+//! By default a client uses the key it was built with, which can be swapped
+//! at runtime with [`Client::set_api_key`](crate::Client::set_api_key). For
+//! credentials managed elsewhere (a secrets manager, a rotating token file),
+//! configure an [`ApiKeyProvider`], which is consulted before every request.
+//! When the API rejects a key with 401, the provider is asked to refresh
+//! and the request is retried once before the error is returned.
+
+use crate::error::Result;
+use crate::transport::async_trait;
+
+/// Supplies the API key used for each request
+///
+/// # Example
+/// ```
+/// use zeroentropy_community::{async_trait, ApiKeyProvider, Client};
+///
+/// struct FromFile(std::path::PathBuf);
+///
+/// #[async_trait]
+/// impl ApiKeyProvider for FromFile {
+///     async fn api_key(&self) -> zeroentropy_community::Result<String> {
+///         Ok(std::fs::read_to_string(&self.0)?.trim().to_string())
+///     }
+/// }
+///
+/// let client = Client::builder()
+///     .api_key_provider(FromFile("/run/secrets/zeroentropy".into()))
+///     .build()
+///     .unwrap();
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ApiKeyProvider: Send + Sync {
+    /// Return the API key to use for the next request
+    async fn api_key(&self) -> Result<String>;
+
+    /// Discard the current key after the API rejected it with 401
+    ///
+    /// Called at most once per call, before it is retried with the key
+    /// [`api_key`](Self::api_key) returns next. The default does nothing,
+    /// which suits providers that read the key fresh every time; providers
+    /// that cache the key should drop or reload it here.
+    async fn refresh(&self) -> Result<()> {
+        Ok(())
+    }
+}
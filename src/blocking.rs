@@ -0,0 +1,979 @@
+//! Synchronous client for use outside of an async runtime
+//!
+//! Each blocking resource wraps its async counterpart and drives it to
+//! completion on a private single-threaded tokio runtime, so CLI tools and
+//! scripts can use the SDK without setting up tokio themselves.
+//!
+//! Calls must not be made from within an async context; doing so panics.
+//!
+//! # Example
+//! ```no_run
+//! use zeroentropy_community::blocking::Client;
+//!
+//! let client = Client::from_env()?;
+//! client.collections().add("my_collection")?;
+//!
+//! let results = client.queries().top_snippets(
+//!     "my_collection",
+//!     "test",
+//!     10,
+//!     None,
+//!     None,
+//!     None,
+//!     None,
+//! )?;
+//! println!("Found {} results", results.results.len());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::client::{ClientBuilder, RequestOptions};
+use crate::error::Result;
+use crate::types::{
+    ApiKeyListResponse, ApiKeyResponse, BatchAddResult, CollectionIndexReport,
+    CollectionListResponse, CollectionResponse, CopyOptions, CopyReport, CreateApiKeyResponse,
+    DeleteAllReport, DocumentContent, DocumentInfo, DocumentInfoListResponse,
+    DocumentInfoResponse, DocumentResponse, DocumentResult, DocumentSpec, Filter, IndexStatus,
+    LatencyMode, Metadata, ModelListResponse, PageInfo, PageInfoResponse, ParseDocumentResponse,
+    ReindexReport, RerankDocument, RerankParams, RerankResponse, Reranker, TopDocumentsParams,
+    TopDocumentsResponse, TopPagesParams, TopPagesResponse, TopSnippetsParams, TopSnippetsResponse,
+};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// Blocking ZeroEntropy API client
+#[derive(Clone)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a new blocking client
+    pub fn new(api_key: impl Into<String>) -> Result<Self> {
+        Self::from_async(crate::Client::new(api_key)?)
+    }
+
+    /// Create a new blocking client from the ZEROENTROPY_API_KEY environment variable
+    pub fn from_env() -> Result<Self> {
+        Self::from_async(crate::Client::from_env()?)
+    }
+
+    /// Create a client builder for advanced configuration
+    ///
+    /// Finish with [`ClientBuilder::build_blocking`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Wrap an existing async client
+    pub fn from_async(inner: crate::Client) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Access the underlying async client
+    pub fn as_async(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Run any future from the async API to completion
+    ///
+    /// Escape hatch for functionality without a blocking wrapper.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Access the collections resource
+    pub fn collections(&self) -> Collections<'_> {
+        Collections {
+            inner: self.inner.collections(),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Access the documents resource
+    pub fn documents(&self) -> Documents<'_> {
+        Documents {
+            inner: self.inner.documents(),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Access the queries resource
+    pub fn queries(&self) -> Queries<'_> {
+        Queries {
+            inner: self.inner.queries(),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Start a fluent search of `collection_name`
+    pub fn search(&self, collection_name: impl Into<String>) -> Search<'_> {
+        self.queries().search(collection_name)
+    }
+
+    /// Access the models resource
+    pub fn models(&self) -> Models<'_> {
+        Models {
+            inner: self.inner.models(),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Access the admin resource
+    pub fn admin(&self) -> Admin<'_> {
+        Admin {
+            inner: self.inner.admin(),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Access the parsers resource
+    pub fn parsers(&self) -> Parsers<'_> {
+        Parsers {
+            inner: self.inner.parsers(),
+            runtime: &self.runtime,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Build a blocking client
+    pub fn build_blocking(self) -> Result<Client> {
+        Client::from_async(self.build()?)
+    }
+}
+
+/// Blocking counterpart of [`crate::Collections`]
+pub struct Collections<'a> {
+    inner: crate::Collections<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Collections<'a> {
+    /// Apply request options to every call made through this handle
+    pub fn with_options(self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Add a new collection
+    pub fn add(&self, collection_name: impl Into<String>) -> Result<CollectionResponse> {
+        self.runtime.block_on(self.inner.add(collection_name))
+    }
+
+    /// Create a collection unless it already exists, returning whether it
+    /// was created
+    pub fn ensure(&self, collection_name: impl Into<String>) -> Result<bool> {
+        self.runtime.block_on(self.inner.ensure(collection_name))
+    }
+
+    /// Wait until no document of a collection is waiting to be parsed or
+    /// indexed
+    pub fn wait_until_indexed(
+        &self,
+        collection_name: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<CollectionIndexReport> {
+        self.runtime
+            .block_on(self.inner.wait_until_indexed(collection_name, timeout))
+    }
+
+    /// Wait until a collection is indexed, passing each polled report to
+    /// `on_progress`
+    pub fn wait_until_indexed_with_progress(
+        &self,
+        collection_name: impl Into<String>,
+        timeout: Duration,
+        on_progress: impl FnMut(&CollectionIndexReport),
+    ) -> Result<CollectionIndexReport> {
+        self.runtime.block_on(self.inner.wait_until_indexed_with_progress(
+            collection_name,
+            timeout,
+            on_progress,
+        ))
+    }
+
+    /// Copy every document of a collection into another collection
+    pub fn copy(
+        &self,
+        source: impl Into<String>,
+        destination: impl Into<String>,
+        options: CopyOptions,
+    ) -> Result<CopyReport> {
+        self.runtime
+            .block_on(self.inner.copy(source, destination, options))
+    }
+
+    /// Delete a collection
+    pub fn delete(&self, collection_name: impl Into<String>) -> Result<CollectionResponse> {
+        self.runtime.block_on(self.inner.delete(collection_name))
+    }
+
+    /// Get list of all collections
+    pub fn get_list(&self) -> Result<CollectionListResponse> {
+        self.runtime.block_on(self.inner.get_list())
+    }
+
+    /// Get every collection with its document count in each index status
+    pub fn get_list_with_stats(&self) -> Result<Vec<CollectionIndexReport>> {
+        self.runtime.block_on(self.inner.get_list_with_stats())
+    }
+}
+
+/// Blocking counterpart of [`crate::Documents`]
+pub struct Documents<'a> {
+    inner: crate::Documents<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Documents<'a> {
+    /// Apply request options to every call made through this handle
+    pub fn with_options(self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Add a document to a collection
+    pub fn add(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        content: DocumentContent,
+        metadata: Option<Metadata>,
+        overwrite: Option<bool>,
+    ) -> Result<DocumentResponse> {
+        self.runtime.block_on(
+            self.inner
+                .add(collection_name, path, content, metadata, overwrite),
+        )
+    }
+
+    /// Add many documents to a collection, reporting each one's outcome
+    pub fn add_batch(
+        &self,
+        collection_name: impl Into<String>,
+        documents: Vec<DocumentSpec>,
+    ) -> Vec<BatchAddResult> {
+        self.runtime
+            .block_on(self.inner.add_batch(collection_name, documents))
+    }
+
+    /// Add a text document
+    pub fn add_text(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        text: impl Into<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.add_text(collection_name, path, text, metadata))
+    }
+
+    /// Add a document whose text is already split into pages
+    pub fn add_text_pages(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        pages: Vec<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.add_text_pages(collection_name, path, pages, metadata))
+    }
+
+    /// Add a PDF document from base64 data
+    pub fn add_pdf(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        base64_data: impl Into<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.add_pdf(collection_name, path, base64_data, metadata))
+    }
+
+    /// Add a text document, replacing any document already at `path`
+    pub fn upsert_text(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        text: impl Into<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.upsert_text(collection_name, path, text, metadata))
+    }
+
+    /// Add a PDF document from base64 data, replacing any document already
+    /// at `path`
+    pub fn upsert_pdf(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        base64_data: impl Into<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.upsert_pdf(collection_name, path, base64_data, metadata))
+    }
+
+    /// Add a document, or update the metadata of the document already at
+    /// `path`
+    pub fn add_or_update(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        content: DocumentContent,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.add_or_update(collection_name, path, content, metadata))
+    }
+
+    /// Add a PDF document from file path
+    pub fn add_pdf_file(
+        &self,
+        collection_name: impl Into<String>,
+        document_path: impl Into<String>,
+        file_path: impl AsRef<std::path::Path>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime.block_on(self.inner.add_pdf_file(
+            collection_name,
+            document_path,
+            file_path,
+            metadata,
+        ))
+    }
+
+    /// Add a document from a file on the web
+    pub fn add_url(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        url: impl AsRef<str>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime
+            .block_on(self.inner.add_url(collection_name, path, url, metadata))
+    }
+
+    /// Add a document from a file on the web, downloading at most
+    /// `max_bytes`
+    pub fn add_url_with_limit(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        url: impl AsRef<str>,
+        metadata: Option<Metadata>,
+        max_bytes: u64,
+    ) -> Result<DocumentResponse> {
+        self.runtime.block_on(self.inner.add_url_with_limit(
+            collection_name,
+            path,
+            url,
+            metadata,
+            max_bytes,
+        ))
+    }
+
+    /// Add a document from a file of any supported type, detected from its
+    /// content
+    pub fn add_file(
+        &self,
+        collection_name: impl Into<String>,
+        document_path: impl Into<String>,
+        file_path: impl AsRef<std::path::Path>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.runtime.block_on(self.inner.add_file(
+            collection_name,
+            document_path,
+            file_path,
+            metadata,
+        ))
+    }
+
+    /// Update a document's metadata or index status
+    pub fn update(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        metadata: Option<Metadata>,
+        index_status: Option<IndexStatus>,
+    ) -> Result<DocumentResponse> {
+        self.runtime.block_on(
+            self.inner
+                .update(collection_name, path, metadata, index_status),
+        )
+    }
+
+    /// Delete a document
+    pub fn delete(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<DocumentResponse> {
+        self.runtime.block_on(self.inner.delete(collection_name, path))
+    }
+
+    /// Change a document's metadata in place, returning the metadata written
+    pub fn patch_metadata(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        patch: impl FnOnce(&mut Metadata),
+    ) -> Result<Metadata> {
+        self.runtime
+            .block_on(self.inner.patch_metadata(collection_name, path, patch))
+    }
+
+    /// Change a document's metadata in place, failing with
+    /// [`Error::ConcurrentModification`](crate::Error::ConcurrentModification)
+    /// if it changed while being patched
+    pub fn patch_metadata_checked(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        patch: impl FnOnce(&mut Metadata),
+    ) -> Result<Metadata> {
+        self.runtime
+            .block_on(self.inner.patch_metadata_checked(collection_name, path, patch))
+    }
+
+    /// Delete every document of a collection, keeping the collection
+    pub fn delete_all(&self, collection_name: impl Into<String>) -> Result<DeleteAllReport> {
+        self.runtime.block_on(self.inner.delete_all(collection_name))
+    }
+
+    /// Submit every document that failed to parse or index again
+    pub fn reindex_failed(&self, collection_name: impl Into<String>) -> Result<ReindexReport> {
+        self.runtime.block_on(self.inner.reindex_failed(collection_name))
+    }
+
+    /// Get document information
+    pub fn get_info(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        include_content: Option<bool>,
+    ) -> Result<DocumentInfoResponse> {
+        self.runtime
+            .block_on(self.inner.get_info(collection_name, path, include_content))
+    }
+
+    /// Wait until a document is indexed and can be queried
+    pub fn wait_until_indexed(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<DocumentInfo> {
+        self.runtime.block_on(self.inner.wait_until_indexed(
+            collection_name,
+            path,
+            timeout,
+            poll_interval,
+        ))
+    }
+
+    /// Get a document's text as a single string
+    pub fn get_text(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<String> {
+        self.runtime.block_on(self.inner.get_text(collection_name, path))
+    }
+
+    /// Get a new signed URL for a document's original file, if it has one
+    pub fn refresh_file_url(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<Option<String>> {
+        self.runtime
+            .block_on(self.inner.refresh_file_url(collection_name, path))
+    }
+
+    /// Download the original file uploaded for a document
+    pub fn get_raw(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<Vec<u8>> {
+        self.runtime.block_on(self.inner.get_raw(collection_name, path))
+    }
+
+    /// Download the original file uploaded for a document to `file_path`
+    pub fn get_raw_to_file(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        file_path: impl AsRef<std::path::Path>,
+    ) -> Result<u64> {
+        self.runtime
+            .block_on(self.inner.get_raw_to_file(collection_name, path, file_path))
+    }
+
+    /// Check whether a document exists
+    pub fn exists(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<bool> {
+        self.runtime.block_on(self.inner.exists(collection_name, path))
+    }
+
+    /// Get list of documents in a collection
+    pub fn get_info_list(
+        &self,
+        collection_name: impl Into<String>,
+        limit: Option<u32>,
+        path_gt: Option<String>,
+    ) -> Result<DocumentInfoListResponse> {
+        self.runtime
+            .block_on(self.inner.get_info_list(collection_name, limit, path_gt))
+    }
+
+    /// Count the documents of a collection by index status
+    pub fn index_report(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Result<CollectionIndexReport> {
+        self.runtime.block_on(self.inner.index_report(collection_name))
+    }
+
+    /// Count the documents of a collection; `filter` must be `None`
+    pub fn count(&self, collection_name: impl Into<String>, filter: Option<Filter>) -> Result<u64> {
+        self.runtime.block_on(self.inner.count(collection_name, filter))
+    }
+
+    /// Get information about a specific page
+    pub fn get_page_info(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        page_number: u32,
+        include_content: Option<bool>,
+    ) -> Result<PageInfoResponse> {
+        self.runtime.block_on(self.inner.get_page_info(
+            collection_name,
+            path,
+            page_number,
+            include_content,
+        ))
+    }
+
+    /// Iterate over the pages of a document, fetching each page only when
+    /// the iterator is advanced to it
+    pub fn pages_iter(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> impl Iterator<Item = Result<PageInfo>> + 'a {
+        use futures_util::StreamExt;
+
+        let runtime = self.runtime;
+        let mut pages = Box::pin(self.inner.pages_stream(collection_name, path));
+        std::iter::from_fn(move || runtime.block_on(pages.next()))
+    }
+
+    /// Get a range of pages of a document, with their content
+    pub fn get_pages(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        pages: std::ops::RangeInclusive<u32>,
+    ) -> Result<Vec<PageInfo>> {
+        self.runtime
+            .block_on(self.inner.get_pages(collection_name, path, pages))
+    }
+}
+
+/// Blocking counterpart of [`crate::Queries`]
+pub struct Queries<'a> {
+    inner: crate::Queries<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Queries<'a> {
+    /// Apply request options to every call made through this handle
+    pub fn with_options(self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Start a fluent search of `collection_name`
+    pub fn search(self, collection_name: impl Into<String>) -> Search<'a> {
+        Search {
+            inner: self.inner.search(collection_name),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Search for top documents matching a query
+    #[allow(clippy::too_many_arguments)]
+    pub fn top_documents(
+        &self,
+        collection_name: impl Into<String>,
+        query: impl Into<String>,
+        k: u32,
+        filter: Option<Filter>,
+        include_metadata: Option<bool>,
+        latency_mode: Option<LatencyMode>,
+        reranker: Option<Reranker>,
+    ) -> Result<TopDocumentsResponse> {
+        self.runtime.block_on(self.inner.top_documents(
+            collection_name,
+            query,
+            k,
+            filter,
+            include_metadata,
+            latency_mode,
+            reranker,
+        ))
+    }
+
+    /// Search for top documents, with optional parameters set through
+    /// [`TopDocumentsParams`]
+    pub fn top_documents_with(&self, params: TopDocumentsParams) -> Result<TopDocumentsResponse> {
+        self.runtime.block_on(self.inner.top_documents_with(params))
+    }
+
+    /// Search for top pages matching a query
+    pub fn top_pages(
+        &self,
+        collection_name: impl Into<String>,
+        query: impl Into<String>,
+        k: u32,
+        filter: Option<Filter>,
+        include_content: Option<bool>,
+        latency_mode: Option<LatencyMode>,
+    ) -> Result<TopPagesResponse> {
+        self.runtime.block_on(self.inner.top_pages(
+            collection_name,
+            query,
+            k,
+            filter,
+            include_content,
+            latency_mode,
+        ))
+    }
+
+    /// Search for top pages, with optional parameters set through
+    /// [`TopPagesParams`]
+    pub fn top_pages_with(&self, params: TopPagesParams) -> Result<TopPagesResponse> {
+        self.runtime.block_on(self.inner.top_pages_with(params))
+    }
+
+    /// Search for top snippets matching a query
+    #[allow(clippy::too_many_arguments)]
+    pub fn top_snippets(
+        &self,
+        collection_name: impl Into<String>,
+        query: impl Into<String>,
+        k: u32,
+        filter: Option<Filter>,
+        include_document_metadata: Option<bool>,
+        precise_responses: Option<bool>,
+        reranker: Option<Reranker>,
+    ) -> Result<TopSnippetsResponse> {
+        self.runtime.block_on(self.inner.top_snippets(
+            collection_name,
+            query,
+            k,
+            filter,
+            include_document_metadata,
+            precise_responses,
+            reranker,
+        ))
+    }
+
+    /// Search for top snippets, with optional parameters set through
+    /// [`TopSnippetsParams`]
+    pub fn top_snippets_with(&self, params: TopSnippetsParams) -> Result<TopSnippetsResponse> {
+        self.runtime.block_on(self.inner.top_snippets_with(params))
+    }
+}
+
+/// Blocking counterpart of [`crate::Search`]
+pub struct Search<'a> {
+    inner: crate::Search<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Search<'a> {
+    fn map(self, f: impl FnOnce(crate::Search<'a>) -> crate::Search<'a>) -> Self {
+        Self {
+            inner: f(self.inner),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Search for `query`
+    pub fn query(self, query: impl Into<String>) -> Self {
+        self.map(|search| search.query(query))
+    }
+
+    /// Return `k` results
+    pub fn k(self, k: u32) -> Self {
+        self.map(|search| search.k(k))
+    }
+
+    /// Only search documents whose metadata matches `filter`
+    pub fn filter(self, filter: impl Into<Filter>) -> Self {
+        self.map(|search| search.filter(filter))
+    }
+
+    /// Set whether results include their document's metadata
+    pub fn include_metadata(self, include: bool) -> Self {
+        self.map(|search| search.include_metadata(include))
+    }
+
+    /// Set whether document and page results include their content
+    pub fn include_content(self, include: bool) -> Self {
+        self.map(|search| search.include_content(include))
+    }
+
+    /// Trade latency against quality for document and page results
+    pub fn latency_mode(self, latency_mode: LatencyMode) -> Self {
+        self.map(|search| search.latency_mode(latency_mode))
+    }
+
+    /// Rerank document and snippet results with the given model
+    pub fn reranker(self, reranker: impl Into<Reranker>) -> Self {
+        self.map(|search| search.reranker(reranker))
+    }
+
+    /// Set whether to return longer snippets
+    pub fn precise(self, precise: bool) -> Self {
+        self.map(|search| search.precise(precise))
+    }
+
+    /// Skip the first `offset` results, e.g. the ones already shown
+    pub fn offset(self, offset: u32) -> Self {
+        self.map(|search| search.offset(offset))
+    }
+
+    /// Run the search for the best matching documents
+    pub fn documents(self) -> Result<TopDocumentsResponse> {
+        self.runtime.block_on(self.inner.documents())
+    }
+
+    /// Run the search for the best matching pages
+    pub fn pages(self) -> Result<TopPagesResponse> {
+        self.runtime.block_on(self.inner.pages())
+    }
+
+    /// Run the search for the best matching snippets
+    pub fn snippets(self) -> Result<TopSnippetsResponse> {
+        self.runtime.block_on(self.inner.snippets())
+    }
+}
+
+/// Blocking counterpart of [`crate::Models`]
+pub struct Models<'a> {
+    inner: crate::Models<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Models<'a> {
+    /// Apply request options to every call made through this handle
+    pub fn with_options(self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime,
+        }
+    }
+
+    /// List the models available to the API key
+    pub fn list(&self) -> Result<ModelListResponse> {
+        self.runtime.block_on(self.inner.list())
+    }
+
+    /// Rerank documents based on relevance to a query
+    pub fn rerank(
+        &self,
+        query: impl Into<String>,
+        documents: Vec<RerankDocument>,
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<RerankResponse> {
+        self.runtime
+            .block_on(self.inner.rerank(query, documents, model_id, top_k))
+    }
+
+    /// Rerank documents, with optional parameters set through
+    /// [`RerankParams`]
+    pub fn rerank_with(&self, params: RerankParams) -> Result<RerankResponse> {
+        self.runtime.block_on(self.inner.rerank_with(params))
+    }
+
+    /// Rerank plain strings, identified in the results by their index in
+    /// `texts`
+    pub fn rerank_texts<S: AsRef<str>>(
+        &self,
+        query: impl Into<String>,
+        texts: &[S],
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<RerankResponse> {
+        self.runtime
+            .block_on(self.inner.rerank_texts(query, texts, model_id, top_k))
+    }
+
+    /// Rerank the snippets of a search response against `query`
+    pub fn rerank_snippets<M>(
+        &self,
+        query: impl Into<String>,
+        response: TopSnippetsResponse<M>,
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<TopSnippetsResponse<M>> {
+        self.runtime
+            .block_on(self.inner.rerank_snippets(query, response, model_id, top_k))
+    }
+
+    /// Rerank the documents of a search response against `query`, using
+    /// `text` for the text of each document
+    pub fn rerank_documents<M>(
+        &self,
+        query: impl Into<String>,
+        response: TopDocumentsResponse<M>,
+        text: impl Fn(&DocumentResult<M>) -> String,
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<TopDocumentsResponse<M>> {
+        self.runtime.block_on(
+            self.inner
+                .rerank_documents(query, response, text, model_id, top_k),
+        )
+    }
+}
+
+/// Blocking counterpart of [`crate::Parsers`]
+pub struct Parsers<'a> {
+    inner: crate::Parsers<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Parsers<'a> {
+    /// Apply request options to every call made through this handle
+    pub fn with_options(self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Parse a PDF or image into text, one string per page
+    pub fn parse_document(&self, base64_data: impl Into<String>) -> Result<ParseDocumentResponse> {
+        self.runtime.block_on(self.inner.parse_document(base64_data))
+    }
+
+    /// Parse a PDF or image from a file path
+    pub fn parse_file(
+        &self,
+        file_path: impl AsRef<std::path::Path>,
+    ) -> Result<ParseDocumentResponse> {
+        self.runtime.block_on(self.inner.parse_file(file_path))
+    }
+}
+
+/// Blocking counterpart of [`crate::Admin`]
+pub struct Admin<'a> {
+    inner: crate::Admin<'a>,
+    runtime: &'a Runtime,
+}
+
+impl<'a> Admin<'a> {
+    /// Apply request options to every call made through this handle
+    pub fn with_options(self, options: RequestOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.with_timeout(timeout),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Create an API key
+    pub fn create_api_key(&self, name: impl Into<String>) -> Result<CreateApiKeyResponse> {
+        self.runtime.block_on(self.inner.create_api_key(name))
+    }
+
+    /// List the organization's API keys
+    pub fn list_api_keys(&self) -> Result<ApiKeyListResponse> {
+        self.runtime.block_on(self.inner.list_api_keys())
+    }
+
+    /// Revoke an API key so it can no longer be used
+    pub fn revoke_api_key(&self, api_key_id: impl Into<String>) -> Result<ApiKeyResponse> {
+        self.runtime.block_on(self.inner.revoke_api_key(api_key_id))
+    }
+}
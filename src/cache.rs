@@ -0,0 +1,120 @@
+//! Response caching for read-only endpoints
+//!
+//! When a [`CacheBackend`] is configured, successful responses from the
+//! query endpoints and the document info endpoints are stored under a key
+//! derived from the API key, base URL, endpoint, query string and request
+//! body, and served from the cache until their TTL expires. Writes are
+//! never cached.
+//!
+//! [`InMemoryCache`] is a bounded LRU cache; implement the trait to share a
+//! cache between processes (Redis) or to use another in-process cache such
+//! as moka.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use zeroentropy_community::{Client, InMemoryCache};
+//!
+//! let client = Client::builder()
+//!     .api_key("test-key")
+//!     .cache(InMemoryCache::new(1_000), Duration::from_secs(300))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::runtime::Instant;
+use crate::transport::async_trait;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Storage for cached response bodies
+///
+/// Keys are opaque hex strings; values are the decoded JSON bodies of
+/// successful responses. Backends should treat failures as misses.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait CacheBackend: Send + Sync {
+    /// Look up a cached body, returning `None` on a miss or once expired
+    async fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+    /// Store a body for at most `ttl`
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration);
+}
+
+/// In-memory LRU cache holding up to a fixed number of responses
+pub struct InMemoryCache {
+    entries: Mutex<LruCache<String, (Vec<u8>, Instant)>>,
+}
+
+impl InMemoryCache {
+    /// Create a cache holding at most `capacity` responses
+    ///
+    /// A capacity of zero is treated as one.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl CacheBackend for InMemoryCache {
+    async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+        let expires_at = Instant::now() + ttl;
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(key.to_string(), (value, expires_at));
+    }
+}
+
+/// Endpoints whose responses may be cached
+const CACHEABLE_ENDPOINTS: &[&str] = &[
+    "/queries/top-documents",
+    "/queries/top-pages",
+    "/queries/top-snippets",
+    "/documents/get-document-info",
+    "/documents/get-document-info-list",
+    "/documents/get-page-info",
+    "/models/get-model-list",
+];
+
+pub(crate) fn is_cacheable(endpoint: &str) -> bool {
+    CACHEABLE_ENDPOINTS.contains(&endpoint)
+}
+
+/// Derive the cache key for a call
+///
+/// The API key is part of the key so that a shared backend never serves
+/// one account's results to another.
+pub(crate) fn cache_key(parts: &[&[u8]]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        // Length-prefix each part so boundaries cannot be shifted
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
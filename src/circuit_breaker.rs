@@ -0,0 +1,62 @@
+//! Client-side circuit breaking during API outages
+
+use crate::error::{Error, Result};
+use crate::runtime::Instant;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Circuit breaker shared by all clones of a client
+///
+/// Opens after `failure_threshold` consecutive failed attempts, where
+/// transport errors and 5xx responses count as failures. While open,
+/// attempts fail immediately with [`Error::CircuitOpen`]. Once `cooldown`
+/// has passed attempts are let through again: the first failure reopens the
+/// circuit and the first success closes it.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Fail fast while the circuit is open
+    pub(crate) fn check(&self) -> Result<()> {
+        let state = self.state();
+        match state.open_until {
+            Some(until) if Instant::now() < until => Err(Error::CircuitOpen),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record the outcome of an attempt
+    pub(crate) fn record(&self, success: bool) {
+        let mut state = self.state();
+        if success {
+            *state = State::default();
+            return;
+        }
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures >= self.failure_threshold {
+            state.open_until = Some(Instant::now() + self.cooldown);
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -1,22 +1,154 @@
-use crate::error::{Error, Result};
-use reqwest::{Client as HttpClient, Response};
+use crate::auth::ApiKeyProvider;
+use crate::cache::{self, CacheBackend};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::error::{ApiError, ApiErrorBody, Error, Result};
+use crate::interceptor::{Interceptor, OnRetry, RetryEvent};
+use crate::rate_limit::RateLimiter;
+use crate::response::{ApiResponse, RateLimitStatus};
+use crate::retry::{ExponentialBackoff, Jitter, RetryPolicy};
+use crate::runtime::{self, Instant};
+use crate::shutdown::{Lifecycle, ShutdownReport};
+use crate::stats::{ClientStats, StatsCounters};
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::transport::BodyStream;
+use crate::types::{Filter, LatencyMode};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::write_queue::{ConflictStrategy, FlushReport, QueuedWrite, WriteQueue};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING, AUTHORIZATION, CONTENT_ENCODING,
+    CONTENT_TYPE, RETRY_AFTER,
+};
+use reqwest::{Client as HttpClient, Method};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
+use tokio::sync::{OnceCell, Semaphore};
+use tokio_util::sync::CancellationToken;
 
-const DEFAULT_BASE_URL: &str = "https://api.zeroentropy.dev/v1";
+/// API root that the version segment is appended to
+const DEFAULT_API_URL: &str = "https://api.zeroentropy.dev";
+/// API version targeted when none is selected
+pub const DEFAULT_API_VERSION: &str = "v1";
+#[cfg(not(target_arch = "wasm32"))]
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
-const DEFAULT_MAX_RETRIES: u32 = 2;
+pub(crate) const DEFAULT_MAX_RETRIES: u32 = 2;
+/// Status codes retried unless configured otherwise
+///
+/// Timeouts, rate limiting and transient server or gateway failures.
+/// Conflicts (409) are not retried as they report state that a retry
+/// cannot change, such as a resource that already exists.
+pub const DEFAULT_RETRYABLE_STATUSES: &[u16] = &[408, 429, 500, 502, 503, 504];
+/// Header carrying the per-call request ID
+const REQUEST_ID_HEADER: &str = "x-request-id";
+/// Header carrying the idempotency key of a write
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Header selecting the organization requests act on
+const ORGANIZATION_HEADER: &str = "x-organization-id";
+/// Header selecting the project requests act on
+const PROJECT_HEADER: &str = "x-project-id";
+/// Endpoint whose conflicts [`ConflictStrategy::Overwrite`] resolves
+#[cfg(not(target_arch = "wasm32"))]
+const ADD_DOCUMENT_ENDPOINT: &str = "/documents/add-document";
+/// Request bodies smaller than this are sent uncompressed
+const MIN_COMPRESSION_SIZE: usize = 1024;
+/// Keyring user under which the API key is stored
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+const KEYRING_USER: &str = "api_key";
+
+/// Compression applied to request bodies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequestCompression {
+    /// Send bodies uncompressed
+    #[default]
+    None,
+    /// Gzip bodies of at least 1 KiB and set `Content-Encoding: gzip`
+    Gzip,
+}
 
 /// ZeroEntropy API client
 #[derive(Clone)]
 pub struct Client {
-    http_client: HttpClient,
-    api_key: String,
-    base_url: String,
-    max_retries: u32,
+    transport: Arc<dyn HttpTransport>,
+    api_key: Arc<RwLock<String>>,
+    api_key_provider: Option<Arc<dyn ApiKeyProvider>>,
+    /// Base URLs in failover order; never empty
+    base_urls: Vec<String>,
+    retry_policy: Arc<dyn RetryPolicy>,
+    /// Time after which a call stops retrying
+    max_elapsed_time: Option<Duration>,
+    /// Whether write endpoints are retried like idempotent ones
+    retry_writes: bool,
+    timeout: Option<Duration>,
+    default_headers: HeaderMap,
+    request_compression: RequestCompression,
+    response_compression: bool,
+    compression_stats: Arc<CompressionCounters>,
+    stats: Arc<StatsCounters>,
+    interceptors: Arc<Vec<Box<dyn Interceptor>>>,
+    /// Caps the number of requests in flight across all clones
+    concurrency_limit: Option<Arc<Semaphore>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// One circuit breaker per entry of `base_urls`, empty when disabled
+    circuit_breakers: Vec<Arc<CircuitBreaker>>,
+    /// Rate-limit headers of the latest response that carried them
+    rate_limit_status: Arc<RwLock<Option<RateLimitStatus>>>,
+    /// In-flight coalesced calls keyed by endpoint, options and body
+    in_flight: Option<Arc<InFlight>>,
+    cache: Option<(Arc<dyn CacheBackend>, Duration)>,
+    /// Delay after which query calls send a second, hedged request
+    hedge_delay: Option<Duration>,
+    lifecycle: Arc<Lifecycle>,
+    default_collection: Option<String>,
+    /// Metadata entries added to document writes and query filters
+    default_metadata: Arc<Vec<(String, String)>>,
+    /// Latency mode of queries that do not set one
+    default_latency_mode: Option<LatencyMode>,
+    organization: Option<String>,
+    project: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    write_queue: Option<Arc<WriteQueue>>,
+}
+
+/// Progress of a call through its attempts
+struct CallState {
+    started: Instant,
+    /// Retries made so far
+    attempts: u32,
+    /// Immediate re-sends to the next base URL made so far
+    failovers: u32,
+    attempt_started: Instant,
+    /// Whether the retry policy is consulted for this call
+    retryable: bool,
+    /// Whether the API key was already refreshed after a 401
+    refreshed_key: bool,
+}
+
+impl CallState {
+    fn new(retryable: bool) -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            attempts: 0,
+            failovers: 0,
+            attempt_started: now,
+            retryable,
+            refreshed_key: false,
+        }
+    }
 }
 
+/// Outcome of a coalesced call, shared by every caller waiting on it
+type SharedCall = OnceCell<std::result::Result<serde_json::Value, Arc<Error>>>;
+type InFlight = Mutex<HashMap<String, Arc<SharedCall>>>;
+
 impl Client {
     /// Create a new ZeroEntropy client
     ///
@@ -42,134 +174,2358 @@ impl Client {
         Self::new(api_key)
     }
 
+    /// Create a new client with the API key stored in the OS keyring
+    ///
+    /// Looks up the entry for `service_name` with the user `api_key` in the
+    /// platform credential store (Keychain, Windows Credential Manager or
+    /// the Secret Service on Linux).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::from_keyring("my-desktop-app").unwrap();
+    /// ```
+    #[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+    pub fn from_keyring(service_name: &str) -> Result<Self> {
+        let api_key = keyring::Entry::new(service_name, KEYRING_USER)
+            .and_then(|entry| entry.get_password())
+            .map_err(|e| match e {
+                keyring::Error::NoEntry => Error::InvalidApiKey,
+                e => Error::InvalidConfig(format!("failed to read API key from keyring: {}", e)),
+            })?;
+        Self::new(api_key)
+    }
+
     /// Create a client builder for advanced configuration
     pub fn builder() -> ClientBuilder {
         ClientBuilder::default()
     }
 
     /// Make a POST request to the API
-    pub(crate) async fn post<T, R>(&self, endpoint: &str, body: &T) -> Result<R>
+    pub(crate) async fn post<T, R>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let _call = self.lifecycle.begin(endpoint)?;
+        let response = Self::guard(options, self.post_attempts(endpoint, body, options)).await?;
+        decode(endpoint, &response.data)
+    }
+
+    /// Make a POST request to an API endpoint, returning the parsed body
+    /// along with the response status and headers
+    ///
+    /// `endpoint` is the path relative to the base URL, e.g.
+    /// `/collections/get-collection-list`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, CollectionListResponse, RequestOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let response = client
+    ///     .post_with_meta::<_, CollectionListResponse>(
+    ///         "/collections/get-collection-list",
+    ///         &serde_json::json!({}),
+    ///         &RequestOptions::new(),
+    ///     )
+    ///     .await?;
+    /// println!(
+    ///     "{} collections, request {:?}, {:?} requests left",
+    ///     response.collections.len(),
+    ///     response.request_id,
+    ///     response.rate_limit_remaining(),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post_with_meta<T, R>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<ApiResponse<R>>
     where
         T: Serialize + ?Sized,
         R: DeserializeOwned,
     {
-        let url = format!("{}{}", self.base_url, endpoint);
+        let _call = self.lifecycle.begin(endpoint)?;
+        let response = Self::guard(options, self.post_attempts(endpoint, body, options)).await?;
+        Ok(ApiResponse {
+            data: decode(endpoint, &response.data)?,
+            status: response.status,
+            headers: response.headers,
+            request_id: response.request_id,
+        })
+    }
+
+    /// Abort a call once its cancellation token fires or its deadline passes
+    async fn guard<F, R>(options: &RequestOptions, call: F) -> Result<R>
+    where
+        F: std::future::Future<Output = Result<R>>,
+    {
+        use futures_util::future::{self, Either};
+
+        if options.cancellation.is_none() && options.deadline.is_none() {
+            return call.await;
+        }
+        let cancelled = async {
+            match &options.cancellation {
+                Some(token) => token.cancelled().await,
+                None => future::pending().await,
+            }
+        };
+        let expired = async {
+            match options.deadline {
+                Some(deadline) => {
+                    runtime::sleep(deadline.saturating_duration_since(Instant::now())).await
+                }
+                None => future::pending().await,
+            }
+        };
+        let interrupted = std::pin::pin!(async {
+            match future::select(std::pin::pin!(cancelled), std::pin::pin!(expired)).await {
+                Either::Left(_) => Error::Cancelled,
+                Either::Right(_) => Error::DeadlineExceeded,
+            }
+        });
+        // Poll the interruption first so a call already cancelled or past its
+        // deadline is not started
+        match future::select(interrupted, std::pin::pin!(call)).await {
+            Either::Left((error, _)) => Err(error),
+            Either::Right((result, _)) => result,
+        }
+    }
+
+    /// Send the attempts of a call, returning the decoded response body
+    async fn post_attempts<T>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<ApiResponse<Vec<u8>>>
+    where
+        T: Serialize + ?Sized,
+    {
+        let request = self.build_request(endpoint, body, options)?;
+
+        let cache_key = match &self.cache {
+            Some((cache, _)) if cache::is_cacheable(endpoint) => {
+                let key = cache::cache_key(&[
+                    self.current_api_key().await?.as_bytes(),
+                    self.organization.as_deref().unwrap_or_default().as_bytes(),
+                    self.project.as_deref().unwrap_or_default().as_bytes(),
+                    request.url.as_bytes(),
+                    &serde_json::to_vec(body)?,
+                ]);
+                let cached = if options.refresh_cache {
+                    None
+                } else {
+                    cache.get(&key).await
+                };
+                if let Some(bytes) = cached {
+                    return Ok(ApiResponse {
+                        data: bytes,
+                        status: 200,
+                        headers: HeaderMap::new(),
+                        request_id: None,
+                    });
+                }
+                Some(key)
+            }
+            _ => None,
+        };
         
-        let mut attempts = 0;
+        let mut call = CallState::new(self.retry_writes || is_idempotent(endpoint));
+        // Index into `base_urls` of the endpoint used for this attempt
+        let mut base = 0;
+        loop {
+            let mut attempt_request = request.clone();
+            if base > 0 {
+                attempt_request.url = self.request_url(&self.base_urls[base], endpoint, options)?;
+            }
+            self.prepare_attempt(&mut attempt_request).await?;
+            options.clamp_to_deadline(&mut attempt_request);
+
+            if let Some(breaker) = self.circuit_breakers.get(base) {
+                // An endpoint known to be down is skipped while others remain
+                if let Err(e) = breaker.check() {
+                    if base + 1 < self.base_urls.len() {
+                        base += 1;
+                        continue;
+                    }
+                    return Err(e);
+                }
+            }
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            let permit = match &self.concurrency_limit {
+                Some(semaphore) => Some(semaphore.acquire().await.expect("semaphore is never closed")),
+                None => None,
+            };
+            call.attempt_started = Instant::now();
+            let result = self.transport.send(attempt_request.clone()).await;
+            drop(permit);
+            self.stats.record_request(
+                endpoint,
+                attempt_request.body.len(),
+                result.as_ref().ok().map(|r| r.status),
+            );
+            if let Some(breaker) = self.circuit_breakers.get(base) {
+                breaker.record(result.as_ref().is_ok_and(|r| r.status < 500));
+            }
+
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    for interceptor in self.interceptors.iter() {
+                        interceptor.on_error(&attempt_request, &e);
+                    }
+                    // Writes that may have reached the API go through
+                    // `backoff`, which only replays them when retryable
+                    let unsent = e.is_connect_error() || (call.retryable && e.is_connection_error());
+                    if base + 1 < self.base_urls.len() && unsent {
+                        self.fail_over(&mut call, &attempt_request, None, &e);
+                        base += 1;
+                        continue;
+                    }
+                    self.backoff(&mut call, &attempt_request, None, e, options)
+                        .await
+                        .map_err(|e| e.in_request(endpoint, call.attempts + 1))?;
+                    base = 0;
+                    continue;
+                }
+            };
+            for interceptor in self.interceptors.iter() {
+                interceptor.on_response(&attempt_request, &response);
+            }
+            self.record_rate_limit(&response.headers);
+
+            let status = response.status;
+            let headers = response.headers.clone();
+            match self.handle_response(endpoint, &attempt_request, response) {
+                Ok(bytes) => {
+                    if let (Some((cache, ttl)), Some(key)) = (&self.cache, &cache_key) {
+                        cache.set(key, bytes.clone(), *ttl).await;
+                    }
+                    let request_id = headers
+                        .get(REQUEST_ID_HEADER)
+                        .or_else(|| attempt_request.headers.get(REQUEST_ID_HEADER))
+                        .and_then(|v| v.to_str().ok())
+                        .map(String::from);
+                    return Ok(ApiResponse {
+                        data: bytes,
+                        status,
+                        headers,
+                        request_id,
+                    });
+                }
+                // Fail over to the next endpoint straight away on server errors
+                Err(e) if base + 1 < self.base_urls.len() && call.retryable && status >= 500 => {
+                    self.fail_over(&mut call, &attempt_request, Some(status), &e);
+                    base += 1;
+                }
+                Err(e) => {
+                    self.backoff(&mut call, &attempt_request, Some(status), e, options)
+                        .await?;
+                    base = 0;
+                }
+            }
+        }
+    }
+
+    /// Make a POST request for a document write
+    ///
+    /// With a write queue configured, writes that cannot reach the API, and
+    /// writes made while earlier ones are still queued, are queued for
+    /// replay and fail with [`Error::WriteQueued`].
+    pub(crate) async fn post_write<T, R>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(queue) = &self.write_queue {
+            // Stay behind queued writes so replays keep their order
+            if !queue.has_pending() {
+                match self.post(endpoint, body, options).await {
+                    Err(e) if is_offline(&e) => {}
+                    result => return result,
+                }
+            }
+            let write = QueuedWrite {
+                endpoint: endpoint.to_string(),
+                body: serde_json::to_value(body)?,
+                idempotency_key: options
+                    .idempotency_key
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            };
+            let _guard = queue.lock().await;
+            queue.push(&write)?;
+            return Err(Error::WriteQueued);
+        }
+        self.post(endpoint, body, options).await
+    }
+
+    /// Replay the document writes queued while the API was unreachable
+    ///
+    /// Writes are sent in the order they were made and removed from the
+    /// queue once the API accepts or rejects them; replaying stops at the
+    /// first write that still cannot reach the API. Conflicts are handled
+    /// according to [`ClientBuilder::write_queue_conflicts`]. Does nothing
+    /// when no write queue is configured.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let report = client.flush_write_queue().await?;
+    /// for (write, error) in &report.rejected {
+    ///     eprintln!("dropped write to {}: {}", write.endpoint, error);
+    /// }
+    /// if report.remaining > 0 {
+    ///     eprintln!("still offline, {} writes queued", report.remaining);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn flush_write_queue(&self) -> Result<FlushReport> {
+        let mut report = FlushReport::default();
+        let Some(queue) = &self.write_queue else {
+            return Ok(report);
+        };
+
+        let _guard = queue.lock().await;
+        let writes = queue.load()?;
+        let mut remaining = Vec::new();
+        for (i, write) in writes.iter().enumerate() {
+            match self.replay_write(write, queue.conflicts()).await {
+                Ok(()) => report.replayed += 1,
+                Err(e) if is_offline(&e) => {
+                    remaining = writes[i..].to_vec();
+                    break;
+                }
+                Err(Error::Conflict(_)) => report.conflicts.push(write.clone()),
+                Err(e) => report.rejected.push((write.clone(), e)),
+            }
+        }
+        report.remaining = remaining.len();
+        queue.store(&remaining)?;
+        Ok(report)
+    }
+
+    /// Send a queued write with its original idempotency key
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn replay_write(&self, write: &QueuedWrite, conflicts: ConflictStrategy) -> Result<()> {
+        let options = RequestOptions::new().idempotency_key(write.idempotency_key.clone());
+        let result = self
+            .post::<_, serde_json::Value>(&write.endpoint, &write.body, &options)
+            .await;
+        match result {
+            Err(Error::Conflict(_))
+                if conflicts == ConflictStrategy::Overwrite
+                    && write.endpoint == ADD_DOCUMENT_ENDPOINT =>
+            {
+                let mut body = write.body.clone();
+                body["overwrite"] = true.into();
+                // A different write from the one the key was issued for
+                let options = RequestOptions::new().idempotency_key(uuid::Uuid::new_v4().to_string());
+                self.post::<_, serde_json::Value>(&write.endpoint, &body, &options)
+                    .await
+                    .map(drop)
+            }
+            result => result.map(drop),
+        }
+    }
+
+    /// Make a POST request, sending a second identical request if the
+    /// first has not completed within the hedge delay
+    ///
+    /// Whichever request completes first provides the result; the other is
+    /// dropped.
+    pub(crate) async fn post_hedged<T, R>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        use futures_util::future::{self, Either};
+
+        let Some(delay) = self.hedge_delay else {
+            return self.post(endpoint, body, options).await;
+        };
+
+        let primary = std::pin::pin!(self.post(endpoint, body, options));
+        let hedge = std::pin::pin!(async {
+            runtime::sleep(delay).await;
+            self.post(endpoint, body, options).await
+        });
+        match future::select(primary, hedge).await {
+            Either::Left((result, _)) | Either::Right((result, _)) => result,
+        }
+    }
+
+    /// Make a POST request, sharing one HTTP call between identical
+    /// concurrent requests when coalescing is enabled
+    pub(crate) async fn post_coalesced<T, R>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<R>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let Some(in_flight) = &self.in_flight else {
+            return self.post_hedged(endpoint, body, options).await;
+        };
+
+        // Only what changes the HTTP exchange; per-caller deadlines and
+        // cancellation are applied to each waiter below
+        let key = serde_json::to_string(&(
+            endpoint,
+            &self.organization,
+            &self.project,
+            &options.headers,
+            &options.query,
+            body,
+        ))?;
+        let call = in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        let mut shared_options = options.clone();
+        shared_options.cancellation = None;
+        shared_options.deadline = None;
+        // A waiter that gives up leaves the call to the others: the cell
+        // stays empty if the one running it is dropped
+        let shared = call.get_or_init(|| async {
+            let outcome = self.post_hedged(endpoint, body, &shared_options).await.map_err(Arc::new);
+            // Later identical requests start a fresh call
+            in_flight.lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+            outcome
+        });
+        let outcome = Self::guard(options, async { Ok(shared.await) }).await?;
+
+        match outcome {
+            Ok(value) => R::deserialize(value).map_err(|source| Error::Decode {
+                endpoint: endpoint.to_string(),
+                body: value.to_string(),
+                source,
+            }),
+            Err(error) => Err(error.duplicate()),
+        }
+    }
+
+    /// Make a POST request and return the response body unread
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) async fn post_stream<T>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<BodyStream>
+    where
+        T: Serialize + ?Sized,
+    {
+        let _call = self.lifecycle.begin(endpoint)?;
+        Self::guard(options, self.post_stream_attempts(endpoint, body, options)).await
+    }
+
+    /// Send the attempts of a streaming call
+    ///
+    /// Retries like [`post`](Self::post) as the retry policy allows, but
+    /// does not fail over, cache or decompress: the response is requested
+    /// uncompressed so it can be consumed as it arrives.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn post_stream_attempts<T>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<BodyStream>
+    where
+        T: Serialize + ?Sized,
+    {
+        use futures_util::TryStreamExt;
+
+        let mut request = self.build_request(endpoint, body, options)?;
+        request.headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+
+        let mut call = CallState::new(self.retry_writes || is_idempotent(endpoint));
         loop {
-            let response = self
-                .http_client
-                .post(&url)
-                .header("Authorization", format!("Bearer {}", self.api_key))
-                .header("Content-Type", "application/json")
-                .json(body)
-                .send()
+            let mut attempt_request = request.clone();
+            self.prepare_attempt(&mut attempt_request).await?;
+            options.clamp_to_deadline(&mut attempt_request);
+
+            if let Some(breaker) = self.circuit_breakers.first() {
+                breaker.check()?;
+            }
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            let permit = match &self.concurrency_limit {
+                Some(semaphore) => Some(semaphore.acquire().await.expect("semaphore is never closed")),
+                None => None,
+            };
+            call.attempt_started = Instant::now();
+            let result = self.transport.send_streaming(attempt_request.clone()).await;
+            drop(permit);
+            self.stats.record_request(
+                endpoint,
+                attempt_request.body.len(),
+                result.as_ref().ok().map(|r| r.status),
+            );
+            if let Some(breaker) = self.circuit_breakers.first() {
+                breaker.record(result.as_ref().is_ok_and(|r| r.status < 500));
+            }
+
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    for interceptor in self.interceptors.iter() {
+                        interceptor.on_error(&attempt_request, &e);
+                    }
+                    self.backoff(&mut call, &attempt_request, None, e, options)
+                        .await
+                        .map_err(|e| e.in_request(endpoint, call.attempts + 1))?;
+                    continue;
+                }
+            };
+            if (200..300).contains(&response.status) {
+                return Ok(response.body);
+            }
+
+            // Error bodies are small; read them whole to report the failure
+            let response = HttpResponse {
+                status: response.status,
+                headers: response.headers,
+                body: response.body.try_concat().await?,
+            };
+            for interceptor in self.interceptors.iter() {
+                interceptor.on_response(&attempt_request, &response);
+            }
+            self.record_rate_limit(&response.headers);
+
+            let status = response.status;
+            let e = self
+                .handle_response(endpoint, &attempt_request, response)
+                .expect_err("non-2xx responses are errors");
+            self.backoff(&mut call, &attempt_request, Some(status), e, options)
                 .await?;
+        }
+    }
 
-            let status = response.status();
-            
-            // Check if we should retry
-            if attempts < self.max_retries && Self::should_retry(status.as_u16()) {
-                attempts += 1;
-                let delay = Self::calculate_retry_delay(attempts);
-                tokio::time::sleep(delay).await;
-                continue;
+    /// Download a file from a signed URL returned by the API
+    ///
+    /// The URL carries its own authorization, so the request is sent
+    /// without the API key or default headers, and is not retried. Errors
+    /// and stats name the URL without its query string, which holds the
+    /// signature.
+    pub(crate) async fn download(&self, url: &str, options: &RequestOptions) -> Result<Vec<u8>> {
+        let endpoint = url.split('?').next().unwrap_or(url);
+        let _call = self.lifecycle.begin(endpoint)?;
+        Self::guard(options, async {
+            let mut request = HttpRequest {
+                method: Method::GET,
+                url: url.to_string(),
+                headers: HeaderMap::new(),
+                body: Vec::new(),
+                timeout: options.timeout.or(self.timeout),
+            };
+            options.clamp_to_deadline(&mut request);
+
+            let result = self.transport.send(request.clone()).await;
+            self.stats
+                .record_request(endpoint, 0, result.as_ref().ok().map(|r| r.status));
+            let response = result.map_err(|e| e.in_request(endpoint, 1))?;
+            self.handle_response(endpoint, &request, response)
+        })
+        .await
+    }
+
+    /// Fetch a resource to ingest, giving up once it exceeds `max_bytes`
+    ///
+    /// Sent like [`download`](Self::download), without the API key or
+    /// default headers and without retries. Returns the response's
+    /// `Content-Type` along with the body.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) async fn fetch(
+        &self,
+        url: &str,
+        max_bytes: u64,
+        options: &RequestOptions,
+    ) -> Result<(Option<String>, Vec<u8>)> {
+        use futures_util::TryStreamExt;
+        use reqwest::header::CONTENT_LENGTH;
+
+        let endpoint = url.split('?').next().unwrap_or(url);
+        let _call = self.lifecycle.begin(endpoint)?;
+        Self::guard(options, async {
+            let mut headers = HeaderMap::new();
+            headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+            let mut request = HttpRequest {
+                method: Method::GET,
+                url: url.to_string(),
+                headers,
+                body: Vec::new(),
+                timeout: options.timeout.or(self.timeout),
+            };
+            options.clamp_to_deadline(&mut request);
+
+            let result = self.transport.send_streaming(request.clone()).await;
+            self.stats
+                .record_request(endpoint, 0, result.as_ref().ok().map(|r| r.status));
+            let mut response = result.map_err(|e| e.in_request(endpoint, 1))?;
+            if !(200..300).contains(&response.status) {
+                let response = HttpResponse {
+                    status: response.status,
+                    headers: response.headers,
+                    body: response.body.try_concat().await?,
+                };
+                return Err(self
+                    .handle_response(endpoint, &request, response)
+                    .expect_err("non-2xx responses are errors"));
+            }
+
+            let too_large = || Error::ResourceTooLarge {
+                url: endpoint.to_string(),
+                limit: max_bytes,
+            };
+            let content_length = response
+                .headers
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            if content_length.is_some_and(|length| length > max_bytes) {
+                return Err(too_large());
+            }
+            let content_type = response
+                .headers
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+
+            let mut body = Vec::new();
+            while let Some(chunk) = response.body.try_next().await? {
+                if (body.len() + chunk.len()) as u64 > max_bytes {
+                    return Err(too_large());
+                }
+                body.extend_from_slice(&chunk);
+            }
+            Ok((content_type, body))
+        })
+        .await
+    }
+
+    /// Ask the retry policy whether to retry after a failed attempt
+    ///
+    /// When it does, and the retry can be expected to complete before the
+    /// deadline and within the maximum elapsed time, notifies interceptors
+    /// and waits out the delay. Otherwise returns the error to fail the
+    /// call with. The retry is expected to take as long as the attempt that
+    /// just failed.
+    ///
+    /// The first 401 of a call with an [`ApiKeyProvider`] is instead retried
+    /// straight away after refreshing the key.
+    async fn backoff(
+        &self,
+        call: &mut CallState,
+        request: &HttpRequest,
+        status: Option<u16>,
+        error: Error,
+        options: &RequestOptions,
+    ) -> Result<()> {
+        if let (Error::AuthenticationError(_), Some(provider)) = (&error, &self.api_key_provider) {
+            if !call.refreshed_key {
+                call.refreshed_key = true;
+                provider.refresh().await?;
+                self.stats.record_retry();
+                return Ok(());
             }
+        }
 
-            return Self::handle_response(response).await;
+        // A write that failed ambiguously may have been applied already;
+        // only a rate-limited one was certainly turned away
+        if !call.retryable && !error.is_rate_limited() {
+            return Err(error);
+        }
+        let delay = match self.retry_policy.should_retry(call.attempts + 1, &error) {
+            Some(delay) => delay,
+            None => return Err(error),
+        };
+        let needed = delay + call.attempt_started.elapsed();
+        if !options.can_wait(needed) {
+            return Err(error);
         }
+        if let Some(budget) = self.max_elapsed_time {
+            if call.started.elapsed() + needed > budget {
+                return Err(Error::RetryBudgetExhausted {
+                    attempts: call.attempts + 1,
+                    last_error: Box::new(error),
+                });
+            }
+        }
+        call.attempts += 1;
+        self.stats.record_retry();
+        let event = RetryEvent {
+            request,
+            attempt: call.attempts + call.failovers,
+            delay,
+            status,
+            error: &error,
+        };
+        for interceptor in self.interceptors.iter() {
+            interceptor.on_retry(&event);
+        }
+        runtime::sleep(delay).await;
+        Ok(())
     }
 
-    /// Handle the API response
-    async fn handle_response<R: DeserializeOwned>(response: Response) -> Result<R> {
-        let status = response.status();
-        
-        if status.is_success() {
-            Ok(response.json().await?)
-        } else {
-            let status_code = status.as_u16();
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            
-            // Try to parse error message from JSON response
-            let message = serde_json::from_str::<serde_json::Value>(&error_text)
-                .ok()
-                .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(String::from))
-                .unwrap_or(error_text);
-            
-            Err(Error::from_status(status_code, message))
+    /// Count a re-send to the next base URL as an immediate retry and
+    /// notify interceptors of it
+    ///
+    /// Failovers do not count against the retry policy.
+    fn fail_over(
+        &self,
+        call: &mut CallState,
+        request: &HttpRequest,
+        status: Option<u16>,
+        error: &Error,
+    ) {
+        call.failovers += 1;
+        self.stats.record_retry();
+        let event = RetryEvent {
+            request,
+            attempt: call.attempts + call.failovers,
+            delay: Duration::ZERO,
+            status,
+            error,
+        };
+        for interceptor in self.interceptors.iter() {
+            interceptor.on_retry(&event);
         }
     }
 
-    /// Check if a status code should trigger a retry
-    fn should_retry(status: u16) -> bool {
-        matches!(status, 408 | 409 | 429) || status >= 500
+    /// Authorize an attempt and let interceptors adjust it
+    async fn prepare_attempt(&self, request: &mut HttpRequest) -> Result<()> {
+        let authorization = format!("Bearer {}", self.current_api_key().await?);
+        request.headers.insert(
+            AUTHORIZATION,
+            parse_header_value("Authorization", &authorization)?,
+        );
+        for interceptor in self.interceptors.iter() {
+            interceptor.on_request(request);
+        }
+        Ok(())
     }
 
-    /// Calculate exponential backoff delay
-    fn calculate_retry_delay(attempt: u32) -> Duration {
-        let base_delay = 500; // milliseconds
-        let max_delay = 8000; // milliseconds
-        let delay = base_delay * 2_u64.pow(attempt - 1);
-        Duration::from_millis(delay.min(max_delay))
+    /// Stop accepting new requests and wait for in-flight ones to finish
+    ///
+    /// Affects all clones of this client: calls started afterwards fail
+    /// with [`Error::ShutDown`]. Waits at most `timeout` and reports the
+    /// calls that were still running. Streamed downloads count as finished
+    /// once their response headers have arrived.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use zeroentropy_community::Client;
+    /// # async fn example(client: Client) {
+    /// let report = client.shutdown(Duration::from_secs(30)).await;
+    /// if !report.is_clean() {
+    ///     eprintln!("abandoned {} requests: {:?}", report.abandoned.len(), report.abandoned);
+    /// }
+    /// # }
+    /// ```
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        self.lifecycle.shutdown(timeout).await
     }
-}
 
-/// Builder for constructing a ZeroEntropy client with custom options
-#[derive(Default)]
-pub struct ClientBuilder {
-    api_key: Option<String>,
-    base_url: Option<String>,
-    timeout: Option<Duration>,
-    max_retries: Option<u32>,
-}
+    /// Rate-limit state reported by the most recent response carrying
+    /// `X-RateLimit-*` headers, shared by all clones of this client
+    ///
+    /// Lets ingestion pipelines slow down before running into 429s.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example(client: Client) {
+    /// if let Some(wait) = client
+    ///     .rate_limit_status()
+    ///     .filter(|status| status.is_exhausted())
+    ///     .and_then(|status| status.reset_in())
+    /// {
+    ///     tokio::time::sleep(wait).await;
+    /// }
+    /// # }
+    /// ```
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        *self.rate_limit_status.read().unwrap_or_else(|e| e.into_inner())
+    }
 
-impl ClientBuilder {
-    /// Set the API key
-    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
-        self
+    fn record_rate_limit(&self, headers: &HeaderMap) {
+        if let Some(status) = RateLimitStatus::from_headers(headers) {
+            *self.rate_limit_status.write().unwrap_or_else(|e| e.into_inner()) = Some(status);
+        }
     }
 
-    /// Set a custom base URL (useful for testing)
-    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
-        self.base_url = Some(base_url.into());
-        self
+    /// Name of the default collection, if one was configured
+    pub fn default_collection_name(&self) -> Option<&str> {
+        self.default_collection.as_deref()
     }
 
-    /// Set the request timeout
-    pub fn timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = Some(timeout);
-        self
+    /// Latency mode of queries that do not set one, if configured
+    pub fn default_latency_mode(&self) -> Option<LatencyMode> {
+        self.default_latency_mode
     }
 
-    /// Set the maximum number of retries
-    pub fn max_retries(mut self, max_retries: u32) -> Self {
-        self.max_retries = Some(max_retries);
-        self
+    /// Organization requests act on, if one was selected
+    pub fn organization(&self) -> Option<&str> {
+        self.organization.as_deref()
     }
 
-    /// Build the client
-    pub fn build(self) -> Result<Client> {
-        let api_key = self.api_key
-            .or_else(|| std::env::var("ZEROENTROPY_API_KEY").ok())
-            .ok_or(Error::InvalidApiKey)?;
+    /// Project requests act on, if one was selected
+    pub fn project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
 
-        let base_url = self.base_url
-            .or_else(|| std::env::var("ZEROENTROPY_BASE_URL").ok())
-            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    /// A client acting on another project of the organization
+    ///
+    /// Shares everything else, including connections, counters and the
+    /// cache, with this client; cached responses are kept apart per project.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder().project("staging").build()?;
+    /// let production = client.with_project("production")?;
+    /// println!("{:?}", production.collections().get_list().await?.collections);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_project(&self, project: impl Into<String>) -> Result<Client> {
+        let project = project.into();
+        parse_header_value(PROJECT_HEADER, &project)?;
+        let mut client = self.clone();
+        client.project = Some(project);
+        Ok(client)
+    }
 
-        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
-        let max_retries = self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+    /// Base URL requests are sent to first, including the API version
+    pub fn base_url(&self) -> &str {
+        &self.base_urls[0]
+    }
 
-        let http_client = HttpClient::builder()
-            .timeout(timeout)
-            .build()?;
+    /// All base URLs in failover order
+    pub fn base_urls(&self) -> &[String] {
+        &self.base_urls
+    }
 
-        Ok(Client {
-            http_client,
-            api_key,
-            base_url,
-            max_retries,
+    /// Per-request timeout applied by the client, if it sets one itself
+    ///
+    /// `None` when the timeout is enforced by the underlying HTTP client.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Time after which a call stops retrying, if limited
+    pub fn max_elapsed_time(&self) -> Option<Duration> {
+        self.max_elapsed_time
+    }
+
+    /// Add the default metadata entries the caller did not set themselves
+    ///
+    /// Metadata that does not serialize to a map is passed through as is.
+    pub(crate) fn with_default_metadata<M: Serialize>(
+        &self,
+        metadata: Option<M>,
+    ) -> Result<Option<serde_json::Value>> {
+        let metadata = metadata.map(serde_json::to_value).transpose()?;
+        if self.default_metadata.is_empty() {
+            return Ok(metadata);
+        }
+
+        let mut metadata = metadata.unwrap_or_else(|| serde_json::json!({}));
+        if let Some(map) = metadata.as_object_mut() {
+            for (key, value) in self.default_metadata.iter() {
+                map.entry(key.clone()).or_insert_with(|| value.clone().into());
+            }
+        }
+        Ok(Some(metadata))
+    }
+
+    /// Restrict a query filter to the default metadata entries
+    ///
+    /// Keys the filter already constrains are left alone.
+    pub(crate) fn with_default_filter(&self, filter: Option<Filter>) -> Option<Filter> {
+        if self.default_metadata.is_empty() {
+            return filter;
+        }
+
+        let mut filter = filter.unwrap_or_default();
+        for (key, value) in self.default_metadata.iter() {
+            filter
+                .entry(key.clone())
+                .or_insert_with(|| serde_json::json!({ "$eq": value }));
+        }
+        Some(filter)
+    }
+
+    /// Replace the API key used for subsequent requests
+    ///
+    /// Takes effect for all clones of this client, so long-running services
+    /// can rotate credentials without rebuilding. Has no effect when an
+    /// [`ApiKeyProvider`] is configured.
+    pub fn set_api_key(&self, api_key: impl Into<String>) {
+        *self.api_key.write().unwrap_or_else(|e| e.into_inner()) = api_key.into();
+    }
+
+    /// Resolve the API key for the next request
+    async fn current_api_key(&self) -> Result<String> {
+        match &self.api_key_provider {
+            Some(provider) => provider.api_key().await,
+            None => Ok(self.api_key.read().unwrap_or_else(|e| e.into_inner()).clone()),
+        }
+    }
+
+    /// Assemble the URL, headers and encoded body for an API call
+    fn build_request<T: Serialize + ?Sized>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        options: &RequestOptions,
+    ) -> Result<HttpRequest> {
+        let url = self.request_url(&self.base_urls[0], endpoint, options)?;
+        let (body, content_encoding) = self.encode_body(body)?;
+
+        let mut headers = self.default_headers.clone();
+        if let Some(organization) = &self.organization {
+            headers.insert(
+                ORGANIZATION_HEADER,
+                parse_header_value(ORGANIZATION_HEADER, organization)?,
+            );
+        }
+        if let Some(project) = &self.project {
+            headers.insert(PROJECT_HEADER, parse_header_value(PROJECT_HEADER, project)?);
+        }
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        if let Some(encoding) = content_encoding {
+            headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        }
+        if self.response_compression {
+            headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate, br"));
+        }
+        if !headers.contains_key(REQUEST_ID_HEADER) {
+            let request_id = options
+                .request_id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            headers.insert(
+                REQUEST_ID_HEADER,
+                parse_header_value(REQUEST_ID_HEADER, &request_id)?,
+            );
+        }
+        if let Some(key) = &options.idempotency_key {
+            headers.insert(
+                IDEMPOTENCY_KEY_HEADER,
+                parse_header_value(IDEMPOTENCY_KEY_HEADER, key)?,
+            );
+        }
+        for (name, value) in &options.headers {
+            let (name, value) = parse_header(name, value)?;
+            headers.insert(name, value);
+        }
+
+        Ok(HttpRequest {
+            method: Method::POST,
+            url,
+            headers,
+            body,
+            timeout: options.timeout.or(self.timeout),
         })
     }
+
+    /// Join a base URL, endpoint and query parameters
+    fn request_url(
+        &self,
+        base_url: &str,
+        endpoint: &str,
+        options: &RequestOptions,
+    ) -> Result<String> {
+        let mut url = reqwest::Url::parse(&format!("{}{}", base_url, endpoint))
+            .map_err(|e| Error::InvalidConfig(format!("invalid base URL: {}", e)))?;
+        if !options.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&options.query);
+        }
+        Ok(url.into())
+    }
+
+    /// Serialize a request body, compressing it if configured
+    ///
+    /// Returns the bytes to send and the `Content-Encoding` to declare.
+    fn encode_body<T: Serialize + ?Sized>(
+        &self,
+        body: &T,
+    ) -> Result<(Vec<u8>, Option<&'static str>)> {
+        let json = serde_json::to_vec(body)?;
+
+        match self.request_compression {
+            RequestCompression::Gzip if json.len() >= MIN_COMPRESSION_SIZE => {
+                use flate2::{write::GzEncoder, Compression};
+                use std::io::Write;
+
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&json)?;
+                Ok((encoder.finish()?, Some("gzip")))
+            }
+            _ => Ok((json, None)),
+        }
+    }
+
+    /// Handle the API response
+    ///
+    /// Returns the decoded body of a successful response.
+    fn handle_response(
+        &self,
+        endpoint: &str,
+        request: &HttpRequest,
+        response: HttpResponse,
+    ) -> Result<Vec<u8>> {
+        let bytes = self.decode_body(&response)?;
+        
+        if (200..300).contains(&response.status) {
+            Ok(bytes)
+        } else {
+            let error_text = String::from_utf8(bytes.clone())
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            
+            // Keep the structured payload and take the message from it
+            let body = serde_json::from_str::<ApiErrorBody>(&error_text).ok();
+            let message = body
+                .as_ref()
+                .and_then(ApiErrorBody::summary)
+                .unwrap_or(error_text);
+            let mut error = ApiError::new(response.status, message)
+                .with_endpoint(endpoint)
+                .with_raw_body(bytes);
+            if let Some(body) = body {
+                error = error.with_body(body);
+            }
+            
+            // Prefer the server's own request ID when it reports one
+            if let Some(request_id) = response
+                .headers
+                .get(REQUEST_ID_HEADER)
+                .or_else(|| request.headers.get(REQUEST_ID_HEADER))
+                .and_then(|v| v.to_str().ok())
+            {
+                error = error.with_request_id(request_id);
+            }
+            if let Some(retry_after) = Self::retry_after(&response) {
+                error = error.with_retry_after(retry_after);
+            }
+            
+            Err(Error::from_api_error(error))
+        }
+    }
+
+    /// Decode a response body according to its `Content-Encoding`
+    fn decode_body(&self, response: &HttpResponse) -> Result<Vec<u8>> {
+        use std::io::Read;
+
+        let encoding = response
+            .headers
+            .get(CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_ascii_lowercase());
+        let wire = &response.body;
+
+        let decoded = match encoding.as_deref() {
+            Some("gzip") | Some("x-gzip") => {
+                let mut decoded = Vec::new();
+                flate2::read::GzDecoder::new(&wire[..]).read_to_end(&mut decoded)?;
+                decoded
+            }
+            Some("deflate") => {
+                let mut decoded = Vec::new();
+                flate2::read::ZlibDecoder::new(&wire[..]).read_to_end(&mut decoded)?;
+                decoded
+            }
+            Some("br") => {
+                let mut decoded = Vec::new();
+                brotli_decompressor::Decompressor::new(&wire[..], 4096)
+                    .read_to_end(&mut decoded)?;
+                decoded
+            }
+            _ => wire.clone(),
+        };
+
+        self.compression_stats.record(encoding.is_some(), wire.len(), decoded.len());
+        Ok(decoded)
+    }
+
+    /// Response compression counters, useful for checking whether the
+    /// server is actually compressing responses
+    pub fn compression_stats(&self) -> CompressionStats {
+        self.compression_stats.snapshot()
+    }
+
+    /// Request, error and retry counters for this client and its clones
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # fn example(client: Client) {
+    /// let stats = client.stats();
+    /// for (endpoint, count) in &stats.requests_by_endpoint {
+    ///     println!("{endpoint}: {count} requests");
+    /// }
+    /// println!("{} retries, {} bytes sent", stats.retries, stats.bytes_sent);
+    /// # }
+    /// ```
+    pub fn stats(&self) -> ClientStats {
+        self.stats.snapshot()
+    }
+
+    /// Delay requested by a 429 or 503 response's `Retry-After` header
+    fn retry_after(response: &HttpResponse) -> Option<Duration> {
+        if !matches!(response.status, 429 | 503) {
+            return None;
+        }
+        response
+            .headers
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after)
+    }
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let api_key = match &self.api_key_provider {
+            Some(_) => "<provider>".to_string(),
+            None => redact_api_key(&self.api_key.read().unwrap_or_else(|e| e.into_inner())),
+        };
+        // Header values may carry credentials of their own
+        let default_headers: Vec<&str> = self.default_headers.keys().map(|k| k.as_str()).collect();
+        f.debug_struct("Client")
+            .field("api_key", &api_key)
+            .field("base_urls", &self.base_urls)
+            .field("timeout", &self.timeout)
+            .field("max_elapsed_time", &self.max_elapsed_time)
+            .field("default_headers", &default_headers)
+            .field("request_compression", &self.request_compression)
+            .field("response_compression", &self.response_compression)
+            .field("default_collection", &self.default_collection)
+            .field("organization", &self.organization)
+            .field("project", &self.project)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Options applied to individual API calls, overriding client-level settings
+///
+/// # Example
+/// ```no_run
+/// # use zeroentropy_community::{Client, RequestOptions};
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::from_env()?;
+///
+/// // Give a large upload more time than the client default
+/// client
+///     .documents()
+///     .with_options(RequestOptions::new().timeout(Duration::from_secs(600)))
+///     .add_pdf_file("my_collection", "big.pdf", "/path/to/big.pdf", None)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) request_id: Option<String>,
+    pub(crate) idempotency_key: Option<String>,
+    pub(crate) cancellation: Option<CancellationToken>,
+    pub(crate) deadline: Option<Instant>,
+    /// Skip cached responses, but still cache the new one
+    pub(crate) refresh_cache: bool,
+}
+
+impl RequestOptions {
+    /// Create empty request options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timeout for this request, replacing the client timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add a header to this request
+    ///
+    /// Replaces a client default header of the same name. Invalid header
+    /// names or values are reported when the request is sent.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Use a caller-chosen request ID instead of a generated UUID
+    ///
+    /// Sent as `X-Request-Id` so the call can be located in server logs.
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Set the `Idempotency-Key` for a write
+    ///
+    /// Document add, update and delete calls generate a key automatically;
+    /// supply one to make a write idempotent across separate invocations,
+    /// e.g. when a job is restarted. Use a fresh key for each distinct write.
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Add a query string parameter to this request
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Abort the call with [`Error::Cancelled`] when `token` is cancelled
+    ///
+    /// Lets an interactive UI drop searches the user navigated away from.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Fail the call with [`Error::DeadlineExceeded`] once `deadline` passes
+    ///
+    /// On native targets this is a `std::time::Instant`; on wasm32 it is
+    /// `web_time::Instant`.
+    ///
+    /// Covers the whole call including retries: attempt timeouts are
+    /// shortened to the time left, and a retry is skipped unless its delay
+    /// plus the duration of the failed attempt still fit before the
+    /// deadline.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Shorten an attempt's timeout to the time left before the deadline
+    fn clamp_to_deadline(&self, request: &mut HttpRequest) {
+        if let Some(deadline) = self.deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            request.timeout = Some(request.timeout.map_or(remaining, |t| t.min(remaining)));
+        }
+    }
+
+    /// Whether `duration` from now is still before the deadline
+    fn can_wait(&self, duration: Duration) -> bool {
+        self.deadline.is_none_or(|deadline| Instant::now() + duration < deadline)
+    }
+}
+
+/// Snapshot of response compression counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Responses received
+    pub responses: u64,
+    /// Responses that arrived with a `Content-Encoding`
+    pub compressed_responses: u64,
+    /// Body bytes received over the wire
+    pub wire_bytes: u64,
+    /// Body bytes after decompression
+    pub decoded_bytes: u64,
+}
+
+#[derive(Default)]
+struct CompressionCounters {
+    responses: AtomicU64,
+    compressed_responses: AtomicU64,
+    wire_bytes: AtomicU64,
+    decoded_bytes: AtomicU64,
+}
+
+impl CompressionCounters {
+    fn record(&self, compressed: bool, wire_bytes: usize, decoded_bytes: usize) {
+        self.responses.fetch_add(1, Ordering::Relaxed);
+        if compressed {
+            self.compressed_responses.fetch_add(1, Ordering::Relaxed);
+        }
+        self.wire_bytes.fetch_add(wire_bytes as u64, Ordering::Relaxed);
+        self.decoded_bytes.fetch_add(decoded_bytes as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> CompressionStats {
+        CompressionStats {
+            responses: self.responses.load(Ordering::Relaxed),
+            compressed_responses: self.compressed_responses.load(Ordering::Relaxed),
+            wire_bytes: self.wire_bytes.load(Ordering::Relaxed),
+            decoded_bytes: self.decoded_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Deserialize a successful response body, keeping it for the error if
+/// it does not match the expected type
+fn decode<R: DeserializeOwned>(endpoint: &str, body: &[u8]) -> Result<R> {
+    serde_json::from_slice(body).map_err(|source| Error::Decode {
+        endpoint: endpoint.to_string(),
+        body: String::from_utf8_lossy(body).into_owned(),
+        source,
+    })
+}
+
+/// Parse a `Retry-After` value given in seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    // Compare as offsets from the epoch: `SystemTime::now` panics on wasm32
+    let at = httpdate::parse_http_date(value)
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    let now = web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .ok()?;
+    Some(at.saturating_sub(now))
+}
+
+/// Whether sending a request to `endpoint` twice has the same effect as
+/// sending it once
+///
+/// Adding, updating and deleting collections and documents, and creating
+/// and revoking API keys, is not.
+fn is_idempotent(endpoint: &str) -> bool {
+    let action = endpoint.rsplit('/').next().unwrap_or(endpoint);
+    !["add-", "update-", "delete-", "create-", "revoke-"]
+        .iter()
+        .any(|prefix| action.starts_with(prefix))
+}
+
+/// Whether a write failed without reaching the API
+///
+/// Timed-out writes are not offline: the API may have applied them, and
+/// replaying them later could duplicate them.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_offline(error: &Error) -> bool {
+    error.is_connect_error() || matches!(error, Error::CircuitOpen)
+}
+
+/// Read and parse an optional numeric environment variable
+fn env_parse<T: std::str::FromStr>(name: &str) -> Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| Error::InvalidConfig(format!("invalid value for {}: {:?}", name, value))),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Mask an API key for display, keeping only its last four characters
+///
+/// Keys too short to hide anything behind those four are masked entirely.
+pub(crate) fn redact_api_key(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
+
+/// Parse a header name/value pair supplied as strings
+fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue)> {
+    let header_name = HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| Error::InvalidConfig(format!("invalid header name '{}'", name)))?;
+    Ok((header_name, parse_header_value(name, value)?))
+}
+
+/// Parse a header value supplied as a string
+fn parse_header_value(name: &str, value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|_| Error::InvalidConfig(format!("invalid value for header '{}'", name)))
+}
+
+/// Builder for constructing a ZeroEntropy client with custom options
+#[derive(Default)]
+pub struct ClientBuilder {
+    api_key: Option<String>,
+    api_key_provider: Option<Arc<dyn ApiKeyProvider>>,
+    base_urls: Vec<String>,
+    api_version: Option<String>,
+    timeout: Option<Duration>,
+    max_retries: Option<u32>,
+    retryable_statuses: Option<Vec<u16>>,
+    retry_jitter: Option<Jitter>,
+    retry_base_delay: Option<Duration>,
+    retry_max_delay: Option<Duration>,
+    max_elapsed_time: Option<Duration>,
+    retry_writes: bool,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    default_headers: Vec<(String, String)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxies: Vec<reqwest::Proxy>,
+    #[cfg(not(target_arch = "wasm32"))]
+    no_proxy: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    root_certificates: Vec<reqwest::Certificate>,
+    #[cfg(not(target_arch = "wasm32"))]
+    built_in_root_certs: Option<bool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    identity: Option<reqwest::Identity>,
+    #[cfg(all(feature = "danger-insecure-tls", not(target_arch = "wasm32")))]
+    accept_invalid_certs: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    http_version: Option<HttpVersion>,
+    #[cfg(not(target_arch = "wasm32"))]
+    tcp_keepalive: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pool_idle_timeout: Option<Option<Duration>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pool_max_idle_per_host: Option<usize>,
+    #[cfg(not(target_arch = "wasm32"))]
+    resolve_overrides: Vec<(String, SocketAddr)>,
+    request_compression: RequestCompression,
+    response_compression: Option<bool>,
+    interceptors: Vec<Box<dyn Interceptor>>,
+    max_concurrent_requests: Option<usize>,
+    rate_limit: Option<(f64, u32)>,
+    circuit_breaker: Option<(u32, Duration)>,
+    coalesce_identical_queries: bool,
+    cache: Option<(Arc<dyn CacheBackend>, Duration)>,
+    hedge_delay: Option<Duration>,
+    default_collection: Option<String>,
+    default_metadata: Vec<(String, String)>,
+    default_latency_mode: Option<LatencyMode>,
+    organization: Option<String>,
+    project: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    write_queue: Option<PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    write_queue_conflicts: ConflictStrategy,
+}
+
+/// HTTP protocol version restriction set on the builder
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy)]
+enum HttpVersion {
+    Http1Only,
+    Http2PriorKnowledge,
+}
+
+impl std::fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let api_key = self.api_key.as_deref().map(redact_api_key);
+        let default_headers: Vec<&str> =
+            self.default_headers.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("ClientBuilder")
+            .field("api_key", &api_key)
+            .field("api_key_provider", &self.api_key_provider.is_some())
+            .field("base_urls", &self.base_urls)
+            .field("api_version", &self.api_version)
+            .field("timeout", &self.timeout)
+            .field("max_retries", &self.max_retries)
+            .field("max_elapsed_time", &self.max_elapsed_time)
+            .field("default_headers", &default_headers)
+            .field("default_collection", &self.default_collection)
+            .field("organization", &self.organization)
+            .field("project", &self.project)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientBuilder {
+    /// Set the API key
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Resolve the API key through a provider before every request
+    ///
+    /// When set, no static API key is required.
+    pub fn api_key_provider(mut self, provider: impl ApiKeyProvider + 'static) -> Self {
+        self.api_key_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Set a custom base URL (useful for testing)
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_urls = vec![base_url.into()];
+        self
+    }
+
+    /// Set an ordered list of base URLs to fail over between
+    ///
+    /// Each call starts at the first URL. On a connection error or a 5xx
+    /// response the request is re-sent to the next URL without delay; the
+    /// usual retries apply once every URL has failed. Failovers are reported
+    /// to [`on_retry`](Interceptor::on_retry) with a zero delay and counted
+    /// in [`ClientStats::retries`], but not against the retry policy. With a
+    /// [`circuit_breaker`](Self::circuit_breaker) each URL has its own, and a
+    /// URL whose circuit is open is skipped. Writes are only failed
+    /// over when they could not connect, unless
+    /// [`retry_writes`](Self::retry_writes) is enabled, since a write that
+    /// timed out or got a 5xx may already have been applied.
+    ///
+    /// # Example
+    /// ```
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .base_urls(["https://eu.example.com/v1", "https://api.zeroentropy.dev/v1"])
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn base_urls<I>(mut self, base_urls: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.base_urls = base_urls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Select the API version, e.g. `"v2"` or a pinned preview version
+    ///
+    /// Without a custom base URL, requests go to
+    /// `https://api.zeroentropy.dev/{version}`. A custom base URL is then
+    /// treated as the API root and the version is appended to it, so it
+    /// should not carry a version segment of its own.
+    ///
+    /// Defaults to [`DEFAULT_API_VERSION`].
+    pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Set the request timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of retries
+    ///
+    /// Ignored when a [`retry_policy`](Self::retry_policy) is set.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Set the response status codes that trigger a retry
+    ///
+    /// Replaces [`DEFAULT_RETRYABLE_STATUSES`]. Ignored when a
+    /// [`retry_policy`](Self::retry_policy) is set.
+    ///
+    /// # Example
+    /// ```
+    /// use zeroentropy_community::{Client, DEFAULT_RETRYABLE_STATUSES};
+    ///
+    /// // Also retry 409 for an API gateway that reports lock contention with it
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .retryable_statuses(DEFAULT_RETRYABLE_STATUSES.iter().copied().chain([409]))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn retryable_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retryable_statuses = Some(statuses.into_iter().collect());
+        self
+    }
+
+    /// Set how retry delays are randomized
+    ///
+    /// Defaults to [`Jitter::Full`]. Ignored when a
+    /// [`retry_policy`](Self::retry_policy) is set.
+    pub fn retry_jitter(mut self, jitter: Jitter) -> Self {
+        self.retry_jitter = Some(jitter);
+        self
+    }
+
+    /// Set the delay before the first retry
+    ///
+    /// Each following retry waits twice as long, up to
+    /// [`retry_max_delay`](Self::retry_max_delay). Defaults to 500ms.
+    /// Ignored when a [`retry_policy`](Self::retry_policy) is set.
+    pub fn retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = Some(delay);
+        self
+    }
+
+    /// Set the longest delay between two attempts
+    ///
+    /// Defaults to 8s. Ignored when a [`retry_policy`](Self::retry_policy)
+    /// is set.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use zeroentropy_community::Client;
+    ///
+    /// // An overnight batch job that rides out long outages
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .max_retries(10)
+    ///     .retry_base_delay(Duration::from_secs(5))
+    ///     .retry_max_delay(Duration::from_secs(300))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn retry_max_delay(mut self, delay: Duration) -> Self {
+        self.retry_max_delay = Some(delay);
+        self
+    }
+
+    /// Stop retrying once a call has been running for `max_elapsed_time`
+    ///
+    /// Caps the total time spent across attempts and retry delays: a retry
+    /// not expected to complete in time, judging by the duration of the
+    /// failed attempt, fails the call with [`Error::RetryBudgetExhausted`]
+    /// instead. Applies to any [`retry_policy`](Self::retry_policy).
+    pub fn max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.max_elapsed_time = Some(max_elapsed_time);
+        self
+    }
+
+    /// Retry adds, updates and deletes of collections and documents, and
+    /// API key changes
+    ///
+    /// Off by default: a write whose response was lost to a network failure
+    /// or server error may have been applied, and sending it again could
+    /// duplicate it. Such writes are only retried after a 429, which the API
+    /// returns before doing anything. Queries and other reads are always
+    /// retried. Applies to any [`retry_policy`](Self::retry_policy).
+    ///
+    /// Document writes carry an `Idempotency-Key`, so enabling this is safe
+    /// when the API deduplicates them.
+    pub fn retry_writes(mut self, enabled: bool) -> Self {
+        self.retry_writes = enabled;
+        self
+    }
+
+    /// Decide retries with a custom policy
+    ///
+    /// Replaces the default [`ExponentialBackoff`] configured by
+    /// [`max_retries`](Self::max_retries),
+    /// [`retryable_statuses`](Self::retryable_statuses),
+    /// [`retry_jitter`](Self::retry_jitter) and the retry delays.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use zeroentropy_community::{Client, ConstantBackoff};
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .retry_policy(ConstantBackoff::new(5, Duration::from_secs(1)))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn retry_policy(mut self, policy: impl RetryPolicy + 'static) -> Self {
+        self.retry_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Use a pre-configured `reqwest::Client` instead of building one
+    ///
+    /// Useful for sharing a connection pool or reusing proxy and TLS settings
+    /// already configured by the application. The client's own settings are
+    /// kept as-is; a timeout set on this builder is applied per request.
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.transport = Some(Arc::new(ReqwestTransport::new(http_client)));
+        self
+    }
+
+    /// Use a `reqwest_middleware` client, composing its middleware stack
+    /// with the SDK
+    ///
+    /// Requires the `middleware` feature. Consider setting `max_retries(0)`
+    /// if the stack already includes a retry middleware.
+    #[cfg(feature = "middleware")]
+    pub fn middleware_client(
+        mut self,
+        client: reqwest_middleware::ClientWithMiddleware,
+    ) -> Self {
+        self.transport = Some(Arc::new(crate::transport::MiddlewareTransport::new(client)));
+        self
+    }
+
+    /// Send requests through a custom transport
+    ///
+    /// Replaces the built-in reqwest transport entirely, so proxy, TLS and
+    /// HTTP version options on this builder have no effect. A timeout set on
+    /// this builder is passed to the transport with each request.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Add a header sent with every request
+    ///
+    /// Can be called multiple times. Invalid header names or values are
+    /// reported when the client is built.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Route requests through a proxy
+    ///
+    /// Without an explicit proxy the `HTTP_PROXY`, `HTTPS_PROXY` and
+    /// `ALL_PROXY` environment variables are honored. SOCKS5 proxy URLs
+    /// (`socks5://...`) require the `socks` feature. Ignored when a custom
+    /// `http_client` is supplied.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zeroentropy_community::Client;
+    ///
+    /// let proxy = reqwest::Proxy::https("http://proxy.internal:3128")?
+    ///     .basic_auth("user", "password");
+    /// let client = Client::builder()
+    ///     .api_key("your-api-key")
+    ///     .proxy(proxy)
+    ///     .build()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Disable all proxies, including those from environment variables
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn no_proxy(mut self) -> Self {
+        self.no_proxy = true;
+        self
+    }
+
+    /// Trust an additional root certificate
+    ///
+    /// Needed when the API is reached through a gateway presenting a
+    /// certificate signed by an internal CA. Ignored when a custom
+    /// `http_client` is supplied.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zeroentropy_community::Client;
+    ///
+    /// let pem = std::fs::read("/etc/ssl/internal-ca.pem")?;
+    /// let client = Client::builder()
+    ///     .api_key("your-api-key")
+    ///     .add_root_certificate(reqwest::Certificate::from_pem(&pem)?)
+    ///     .build()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Enable or disable the system's built-in root certificates
+    ///
+    /// Disable to trust only certificates added with `add_root_certificate`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.built_in_root_certs = Some(enabled);
+        self
+    }
+
+    /// Present a client certificate for mutual TLS
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn identity(mut self, identity: reqwest::Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Accept any TLS certificate, including self-signed and expired ones
+    ///
+    /// Only for local development against a mock of the API served over
+    /// HTTPS. This disables server authentication entirely; never enable it
+    /// against a real endpoint. Requires the `danger-insecure-tls` feature.
+    #[cfg(all(feature = "danger-insecure-tls", not(target_arch = "wasm32")))]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Only use HTTP/1.1
+    ///
+    /// Useful behind proxies that cannot handle HTTP/2. By default the
+    /// protocol is negotiated via ALPN.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http1_only(mut self) -> Self {
+        self.http_version = Some(HttpVersion::Http1Only);
+        self
+    }
+
+    /// Always use HTTP/2 without negotiating
+    ///
+    /// Lets many concurrent requests share one multiplexed connection. The
+    /// server (or gateway) must support HTTP/2.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.http_version = Some(HttpVersion::Http2PriorKnowledge);
+        self
+    }
+
+    /// Resolve `domain` to a fixed address instead of using DNS
+    ///
+    /// The URL, `Host` header and TLS server name are unchanged, which suits
+    /// split-horizon DNS setups and pointing tests at a local stub. The port
+    /// of `addr` is ignored in favour of the URL's port. Can be called
+    /// multiple times.
+    ///
+    /// # Example
+    /// ```
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .resolve("api.zeroentropy.dev", "10.0.0.12:443".parse().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve_overrides.push((domain.into(), addr));
+        self
+    }
+
+    /// Send TCP keepalive probes on idle connections at this interval
+    ///
+    /// Keeps NATs and load balancers from silently dropping connections
+    /// that long-running services hold open between requests.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Close pooled connections that have been idle for longer than this
+    ///
+    /// Pass `None` to keep idle connections indefinitely. Set it below the
+    /// idle timeout of any proxy in between to avoid reusing connections
+    /// the proxy has already closed. Defaults to 90 seconds.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Keep at most this many idle connections per host
+    ///
+    /// `0` disables connection reuse entirely.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Compress request bodies
+    ///
+    /// Worthwhile for base64-encoded PDF uploads, which produce
+    /// multi-megabyte JSON bodies. The server must accept the encoding.
+    pub fn request_compression(mut self, compression: RequestCompression) -> Self {
+        self.request_compression = compression;
+        self
+    }
+
+    /// Ask the server for compressed responses (enabled by default)
+    ///
+    /// Sends `Accept-Encoding: gzip, deflate, br` and transparently decodes
+    /// the response, which speeds up large `get_info` calls that include
+    /// document content. See [`Client::compression_stats`] to check whether
+    /// compression is being applied.
+    pub fn response_compression(mut self, enabled: bool) -> Self {
+        self.response_compression = Some(enabled);
+        self
+    }
+
+    /// Register an interceptor invoked around every HTTP exchange
+    ///
+    /// Can be called multiple times; interceptors run in registration order.
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Call `callback` before every retry
+    ///
+    /// Shorthand for an [`Interceptor`] that only implements
+    /// [`on_retry`](Interceptor::on_retry); use it to log or count retries,
+    /// which otherwise happen silently.
+    ///
+    /// # Example
+    /// ```
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .on_retry(|event| {
+    ///         eprintln!(
+    ///             "retry {} in {:?} after {}",
+    ///             event.attempt, event.delay, event.error
+    ///         );
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn on_retry<F>(self, callback: F) -> Self
+    where
+        F: Fn(&RetryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.interceptor(OnRetry(callback))
+    }
+
+    /// Limit how many requests may be in flight at once
+    ///
+    /// The limit is shared by all clones of the client. Calls beyond it wait
+    /// for a slot before being sent; retry delays do not hold a slot.
+    pub fn max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /// Throttle requests client-side with a token bucket
+    ///
+    /// Allows bursts of up to `burst` requests, refilled at
+    /// `requests_per_second`. Keeps bulk jobs under the account's rate limit
+    /// instead of relying on 429 retries. Every attempt, including retries,
+    /// counts against the limit, which is shared by all clones of the client.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+
+    /// Fail fast during outages with a circuit breaker
+    ///
+    /// After `failure_threshold` consecutive failed attempts (transport
+    /// errors or 5xx responses) calls fail immediately with
+    /// [`Error::CircuitOpen`] for `cooldown`, instead of waiting on an API
+    /// that is down. Afterwards requests go through again; the first
+    /// failure reopens the circuit. Shared by all clones of the client.
+    /// With several [`base_urls`](Self::base_urls) each URL has its own
+    /// breaker, and calls fail over past the ones that are open.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .circuit_breaker(5, Duration::from_secs(30))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn circuit_breaker(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker = Some((failure_threshold, cooldown));
+        self
+    }
+
+    /// Share one HTTP call between identical concurrent `top_snippets` queries
+    ///
+    /// When enabled, a snippet query issued while an identical one (same
+    /// collection, arguments, and request headers and query parameters) is
+    /// still in flight waits for that call and receives a copy of its
+    /// response instead of sending a request of its own. Each caller keeps
+    /// its own deadline and cancellation token. Disabled by default.
+    pub fn coalesce_identical_queries(mut self, enabled: bool) -> Self {
+        self.coalesce_identical_queries = enabled;
+        self
+    }
+
+    /// Cache responses of read-only endpoints for `ttl`
+    ///
+    /// Applies to the query endpoints and the document info endpoints; see
+    /// [`CacheBackend`] for how keys are derived.
+    pub fn cache(mut self, backend: impl CacheBackend + 'static, ttl: Duration) -> Self {
+        self.cache = Some((Arc::new(backend), ttl));
+        self
+    }
+
+    /// Hedge query requests that have not completed after `delay`
+    ///
+    /// When a `top_documents`, `top_pages` or `top_snippets` call is still
+    /// waiting after `delay`, an identical request is sent and whichever
+    /// completes first is used. Cuts tail latency for interactive search at
+    /// the cost of extra requests; pick a delay around the p95 latency.
+    pub fn hedge_queries(mut self, delay: Duration) -> Self {
+        self.hedge_delay = Some(delay);
+        self
+    }
+
+    /// Act on the given organization, for keys with access to several
+    ///
+    /// Sent as the `X-Organization-Id` header.
+    pub fn organization(mut self, organization: impl Into<String>) -> Self {
+        self.organization = Some(organization.into());
+        self
+    }
+
+    /// Act on the given project of the organization
+    ///
+    /// Sent as the `X-Project-Id` header. See [`Client::with_project`] to
+    /// reach other projects from the same client.
+    pub fn project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+
+    /// Set the collection returned by [`Client::collection`]
+    pub fn default_collection(mut self, name: impl Into<String>) -> Self {
+        self.default_collection = Some(name.into());
+        self
+    }
+
+    /// Add a metadata entry applied to every document and query call
+    ///
+    /// Documents added or updated get the entry unless their metadata sets
+    /// the key, and queries are filtered on it with `$eq` unless their
+    /// filter already constrains the key. Suits scoping a whole application
+    /// to a tenant:
+    ///
+    /// ```
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .default_collection("support_articles")
+    ///     .default_metadata("tenant_id", "acme")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn default_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Use `latency_mode` for document and page queries that do not set
+    /// their own
+    ///
+    /// A query that sets [`LatencyMode::Default`] still gets the API's
+    /// default mode.
+    ///
+    /// ```
+    /// use zeroentropy_community::{Client, LatencyMode};
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .default_latency_mode(LatencyMode::Low)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn default_latency_mode(mut self, latency_mode: LatencyMode) -> Self {
+        self.default_latency_mode = Some(latency_mode);
+        self
+    }
+
+    /// Queue document writes that cannot reach the API in the file at `path`
+    ///
+    /// Document adds, updates and deletes that fail to connect, or are
+    /// stopped by an open circuit breaker, are appended to the file,
+    /// surviving restarts, and fail with [`Error::WriteQueued`]. Writes that
+    /// time out are returned as errors instead, since the API may have
+    /// applied them. Later writes queue up behind queued ones until
+    /// [`Client::flush_write_queue`] has replayed the backlog, e.g. once
+    /// connectivity returns.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zeroentropy_community::{Client, ConflictStrategy};
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("test-key")
+    ///     .write_queue("/var/lib/ingest/zeroentropy-queue.jsonl")
+    ///     .write_queue_conflicts(ConflictStrategy::Overwrite)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_queue(mut self, path: impl Into<PathBuf>) -> Self {
+        self.write_queue = Some(path.into());
+        self
+    }
+
+    /// Set how replayed writes that conflict with the current state are
+    /// handled; defaults to [`ConflictStrategy::Skip`]
+    ///
+    /// Only takes effect together with [`write_queue`](Self::write_queue).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_queue_conflicts(mut self, conflicts: ConflictStrategy) -> Self {
+        self.write_queue_conflicts = conflicts;
+        self
+    }
+
+    /// Build the client
+    ///
+    /// Settings not given to the builder are read from the environment,
+    /// then fall back to the defaults:
+    ///
+    /// | Setting | Environment variable | Default |
+    /// |---|---|---|
+    /// | API key | `ZEROENTROPY_API_KEY` | required |
+    /// | Base URL | `ZEROENTROPY_BASE_URL` | `https://api.zeroentropy.dev/v1` |
+    /// | Timeout | `ZEROENTROPY_TIMEOUT_SECS` | 60 seconds |
+    /// | Max retries | `ZEROENTROPY_MAX_RETRIES` | 2 |
+    /// | Organization | `ZEROENTROPY_ORGANIZATION_ID` | none |
+    /// | Project | `ZEROENTROPY_PROJECT_ID` | none |
+    ///
+    /// Explicit builder values always take precedence over the environment.
+    pub fn build(mut self) -> Result<Client> {
+        if self.timeout.is_none() {
+            self.timeout = env_parse::<u64>("ZEROENTROPY_TIMEOUT_SECS")?.map(Duration::from_secs);
+        }
+        if self.max_retries.is_none() {
+            self.max_retries = env_parse("ZEROENTROPY_MAX_RETRIES")?;
+        }
+        let organization = self
+            .organization
+            .take()
+            .or_else(|| std::env::var("ZEROENTROPY_ORGANIZATION_ID").ok());
+        let project = self
+            .project
+            .take()
+            .or_else(|| std::env::var("ZEROENTROPY_PROJECT_ID").ok());
+        for (name, value) in [(ORGANIZATION_HEADER, &organization), (PROJECT_HEADER, &project)] {
+            if let Some(value) = value {
+                parse_header_value(name, value)?;
+            }
+        }
+
+        let api_key = match self.api_key.take()
+            .or_else(|| std::env::var("ZEROENTROPY_API_KEY").ok())
+        {
+            Some(api_key) => api_key,
+            None if self.api_key_provider.is_some() => String::new(),
+            None => return Err(Error::InvalidApiKey),
+        };
+
+        let mut base_urls = std::mem::take(&mut self.base_urls);
+        if base_urls.is_empty() {
+            if let Ok(base_url) = std::env::var("ZEROENTROPY_BASE_URL") {
+                base_urls.push(base_url);
+            }
+        }
+        let base_urls = match (base_urls.is_empty(), &self.api_version) {
+            (true, version) => vec![format!(
+                "{}/{}",
+                DEFAULT_API_URL,
+                version.as_deref().unwrap_or(DEFAULT_API_VERSION)
+            )],
+            (false, Some(version)) => base_urls
+                .into_iter()
+                .map(|url| format!("{}/{}", url.trim_end_matches('/'), version))
+                .collect(),
+            (false, None) => base_urls,
+        };
+
+        let retry_policy = match self.retry_policy.take() {
+            Some(policy) => policy,
+            None => {
+                let mut policy =
+                    ExponentialBackoff::new(self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES));
+                if let Some(statuses) = self.retryable_statuses.take() {
+                    policy = policy.retryable_statuses(statuses);
+                }
+                if let Some(jitter) = self.retry_jitter {
+                    policy = policy.jitter(jitter);
+                }
+                if let (Some(base), Some(max)) = (self.retry_base_delay, self.retry_max_delay) {
+                    if base > max {
+                        return Err(Error::InvalidConfig(
+                            "retry base delay exceeds the retry max delay".to_string(),
+                        ));
+                    }
+                }
+                if let Some(delay) = self.retry_base_delay {
+                    policy = policy.initial_delay(delay);
+                }
+                if let Some(delay) = self.retry_max_delay {
+                    policy = policy.max_delay(delay);
+                }
+                Arc::new(policy)
+            }
+        };
+        if let Some((requests_per_second, burst)) = self.rate_limit {
+            if !(requests_per_second.is_finite() && requests_per_second > 0.0) || burst == 0 {
+                return Err(Error::InvalidConfig(
+                    "rate limit needs a positive rate and a burst of at least 1".to_string(),
+                ));
+            }
+        }
+        if matches!(self.circuit_breaker, Some((0, _))) {
+            return Err(Error::InvalidConfig(
+                "circuit breaker failure threshold must be at least 1".to_string(),
+            ));
+        }
+        if self.max_concurrent_requests == Some(0) {
+            return Err(Error::InvalidConfig(
+                "max_concurrent_requests must be at least 1".to_string(),
+            ));
+        }
+
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in &self.default_headers {
+            let (name, value) = parse_header(name, value)?;
+            default_headers.append(name, value);
+        }
+
+        let (transport, timeout) = match self.transport.take() {
+            Some(transport) => (transport, self.timeout),
+            None => {
+                let transport: Arc<dyn HttpTransport> =
+                    Arc::new(ReqwestTransport::new(self.build_http_client()?));
+                (transport, None)
+            }
+        };
+        let circuit_breakers = match self.circuit_breaker {
+            Some((threshold, cooldown)) => base_urls
+                .iter()
+                .map(|_| Arc::new(CircuitBreaker::new(threshold, cooldown)))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(Client {
+            transport,
+            api_key: Arc::new(RwLock::new(api_key)),
+            api_key_provider: self.api_key_provider.take(),
+            base_urls,
+            retry_policy,
+            max_elapsed_time: self.max_elapsed_time,
+            retry_writes: self.retry_writes,
+            timeout,
+            default_headers,
+            request_compression: self.request_compression,
+            response_compression: self.response_compression.unwrap_or(true),
+            compression_stats: Arc::new(CompressionCounters::default()),
+            stats: Arc::new(StatsCounters::default()),
+            interceptors: Arc::new(std::mem::take(&mut self.interceptors)),
+            concurrency_limit: self.max_concurrent_requests.map(|max| Arc::new(Semaphore::new(max))),
+            rate_limiter: self
+                .rate_limit
+                .map(|(requests_per_second, burst)| Arc::new(RateLimiter::new(requests_per_second, burst))),
+            circuit_breakers,
+            rate_limit_status: Arc::new(RwLock::new(None)),
+            in_flight: self
+                .coalesce_identical_queries
+                .then(|| Arc::new(Mutex::new(HashMap::new()))),
+            cache: self.cache.take(),
+            hedge_delay: self.hedge_delay,
+            lifecycle: Arc::new(Lifecycle::default()),
+            default_collection: self.default_collection.take(),
+            organization,
+            project,
+            default_metadata: Arc::new(std::mem::take(&mut self.default_metadata)),
+            default_latency_mode: self.default_latency_mode,
+            #[cfg(not(target_arch = "wasm32"))]
+            write_queue: match self.write_queue.take() {
+                Some(path) => Some(Arc::new(WriteQueue::open(path, self.write_queue_conflicts)?)),
+                None => None,
+            },
+        })
+    }
+
+    /// Build the underlying reqwest client from the transport options
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_http_client(&mut self) -> Result<HttpClient> {
+        let mut http_builder = HttpClient::builder()
+            .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT));
+        for proxy in std::mem::take(&mut self.proxies) {
+            http_builder = http_builder.proxy(proxy);
+        }
+        if self.no_proxy {
+            http_builder = http_builder.no_proxy();
+        }
+        for certificate in std::mem::take(&mut self.root_certificates) {
+            http_builder = http_builder.add_root_certificate(certificate);
+        }
+        if let Some(enabled) = self.built_in_root_certs {
+            http_builder = http_builder.tls_built_in_root_certs(enabled);
+        }
+        if let Some(identity) = self.identity.take() {
+            http_builder = http_builder.identity(identity);
+        }
+        #[cfg(feature = "danger-insecure-tls")]
+        if self.accept_invalid_certs {
+            http_builder = http_builder.danger_accept_invalid_certs(true);
+        }
+        match self.http_version {
+            Some(HttpVersion::Http1Only) => {
+                http_builder = http_builder.http1_only();
+            }
+            Some(HttpVersion::Http2PriorKnowledge) => {
+                http_builder = http_builder.http2_prior_knowledge();
+            }
+            None => {}
+        }
+        for (domain, addr) in &self.resolve_overrides {
+            http_builder = http_builder.resolve(domain, *addr);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            http_builder = http_builder.tcp_keepalive(interval);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            http_builder = http_builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            http_builder = http_builder.pool_max_idle_per_host(max);
+        }
+        Ok(http_builder.build()?)
+    }
+
+    /// Build the fetch-based client used on wasm32
+    ///
+    /// Connections, proxies and TLS are managed by the host environment.
+    #[cfg(target_arch = "wasm32")]
+    fn build_http_client(&mut self) -> Result<HttpClient> {
+        Ok(HttpClient::builder().build()?)
+    }
 }
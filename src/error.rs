@@ -1,62 +1,102 @@
+use crate::types::IndexStatus;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type for ZeroEntropy operations
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Error types for the ZeroEntropy SDK
+///
+/// New variants may be added in minor releases; match on [`Error::kind`]
+/// for a stable classification.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// HTTP request failed
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
 
-    /// API returned an error status code
-    #[error("API error ({status}): {message}")]
-    Api {
-        status: u16,
-        message: String,
+    /// A custom transport failed to complete the request
+    #[error("Transport error: {0}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// A call gave up after its requests failed in transit
+    ///
+    /// Wraps the [`Error::Http`] or [`Error::Transport`] of the last
+    /// attempt, which is also its [`source`](std::error::Error::source).
+    #[error("Request to {endpoint} failed after {attempts} attempt(s): {source}")]
+    Request {
+        /// API endpoint of the call
+        endpoint: String,
+        /// Attempts made, including the first
+        attempts: u32,
+        /// The transport failure of the last attempt
+        #[source]
+        source: Box<Error>,
     },
 
+    /// API returned an error status code
+    #[error("API error ({}): {}", .0.status, .0.message)]
+    Api(ApiError),
+
     /// Bad request (400)
     #[error("Bad request: {0}")]
-    BadRequest(String),
+    BadRequest(ApiError),
 
     /// Authentication error (401)
     #[error("Authentication failed: {0}")]
-    AuthenticationError(String),
+    AuthenticationError(ApiError),
 
     /// Permission denied (403)
     #[error("Permission denied: {0}")]
-    PermissionDenied(String),
+    PermissionDenied(ApiError),
 
     /// Resource not found (404)
     #[error("Not found: {0}")]
-    NotFound(String),
+    NotFound(ApiError),
 
     /// Conflict (409) - resource already exists
     #[error("Conflict: {0}")]
-    Conflict(String),
+    Conflict(ApiError),
 
     /// Unprocessable entity (422)
     #[error("Unprocessable entity: {0}")]
-    UnprocessableEntity(String),
+    UnprocessableEntity(ApiError),
 
     /// Rate limit exceeded (429)
     #[error("Rate limit exceeded: {0}")]
-    RateLimitExceeded(String),
+    RateLimitExceeded(ApiError),
 
     /// Internal server error (500+)
     #[error("Internal server error: {0}")]
-    InternalServerError(String),
+    InternalServerError(ApiError),
 
     /// Failed to serialize/deserialize JSON
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// A successful response body did not match the expected type
+    #[error("Failed to decode response from {endpoint}: {source}")]
+    Decode {
+        /// API endpoint of the call
+        endpoint: String,
+        /// Response body as received
+        body: String,
+        /// The deserialization failure
+        #[source]
+        source: serde_json::Error,
+    },
+
     /// Invalid API key
     #[error("Invalid API key: API key must be provided either via constructor or ZEROENTROPY_API_KEY environment variable")]
     InvalidApiKey,
 
+    /// Invalid client configuration
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -64,21 +104,592 @@ pub enum Error {
     /// Base64 decoding error
     #[error("Base64 error: {0}")]
     Base64(#[from] base64::DecodeError),
+
+    /// The call was cancelled through its cancellation token
+    #[error("Request cancelled")]
+    Cancelled,
+
+    /// The call did not complete before its deadline
+    #[error("Request deadline exceeded")]
+    DeadlineExceeded,
+
+    /// The client has been shut down and accepts no new requests
+    #[error("Client has been shut down")]
+    ShutDown,
+
+    /// The circuit breaker is open after repeated failures; the request
+    /// was not sent
+    #[error("Circuit breaker open: too many consecutive failures")]
+    CircuitOpen,
+
+    /// A document write could not reach the API and was added to the
+    /// client's write queue for replay
+    #[error("Document write queued for replay")]
+    WriteQueued,
+
+    /// A document being waited on failed to parse or index
+    #[error(
+        "Document {path} failed with status {status:?}: {}",
+        .reason.as_deref().unwrap_or("no reason given")
+    )]
+    IndexingFailed {
+        /// Path of the document
+        path: String,
+        /// [`IndexStatus::ParsingFailed`] or [`IndexStatus::IndexingFailed`]
+        status: IndexStatus,
+        /// Why it failed, when the API reports it
+        reason: Option<String>,
+    },
+
+    /// Waiting for indexing to finish took longer than allowed
+    #[error("Timed out after {0:?} waiting for indexing")]
+    WaitTimeout(Duration),
+
+    /// A resource fetched for ingestion was larger than allowed
+    #[error("{url} is larger than the limit of {limit} bytes")]
+    ResourceTooLarge {
+        /// URL of the resource, without its query string
+        url: String,
+        /// Size limit in bytes
+        limit: u64,
+    },
+
+    /// A resource fetched for ingestion is of a type that cannot be
+    /// indexed
+    #[error("{url} has unsupported content type {content_type}")]
+    UnsupportedContentType {
+        /// URL of the resource, without its query string
+        url: String,
+        /// `Content-Type` the server sent
+        content_type: String,
+    },
+
+    /// A document's metadata changed while
+    /// [`Documents::patch_metadata_checked`](crate::Documents::patch_metadata_checked)
+    /// was patching it; nothing was written
+    ///
+    /// Detected by the client re-reading the document, not reported by the
+    /// API.
+    #[error("Metadata of {path} changed while being patched")]
+    ConcurrentModification {
+        /// Path of the document
+        path: String,
+    },
+
+    /// A document has no original file stored, e.g. because it was added
+    /// as text
+    #[error("Document {path} has no stored file")]
+    NoStoredFile {
+        /// Path of the document
+        path: String,
+    },
+
+    /// A request parameter is outside the range the API accepts; no
+    /// request was sent
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    /// Retrying stopped because the next retry would not have completed
+    /// within the client's maximum elapsed time
+    #[error("Retry budget exhausted after {attempts} attempts: {last_error}")]
+    RetryBudgetExhausted {
+        /// Attempts made, including the first
+        attempts: u32,
+        /// Error of the last attempt
+        #[source]
+        last_error: Box<Error>,
+    },
+}
+
+/// Stable classification of an [`Error`]
+///
+/// Several variants may share a kind, and kinds may be added in minor
+/// releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The request could not be sent or no response was received
+    Transport,
+    /// The request was malformed or failed validation (400, 422)
+    InvalidRequest,
+    /// The API key was missing or rejected (401, or none configured)
+    Authentication,
+    /// The API key lacks access to the resource (403)
+    PermissionDenied,
+    /// The resource does not exist (404)
+    NotFound,
+    /// The request conflicts with the current state (409)
+    Conflict,
+    /// The rate limit was exceeded (429)
+    RateLimited,
+    /// The API failed to process the request (5xx)
+    Server,
+    /// Any other API error status
+    Api,
+    /// A body could not be encoded or decoded
+    Serialization,
+    /// The client configuration is invalid
+    Configuration,
+    /// Reading or writing local data failed
+    Io,
+    /// The call was cancelled by the caller
+    Cancelled,
+    /// The call did not complete before its deadline
+    DeadlineExceeded,
+    /// The client did not send the request: it was shut down, its circuit
+    /// breaker was open or the write was queued for later
+    NotSent,
+    /// A document failed to parse or index
+    IndexingFailed,
 }
 
 impl Error {
+    /// Stable classification of this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Http(_) | Error::Transport(_) => ErrorKind::Transport,
+            Error::BadRequest(_)
+            | Error::UnprocessableEntity(_)
+            | Error::ResourceTooLarge { .. }
+            | Error::UnsupportedContentType { .. }
+            | Error::InvalidParameter(_) => ErrorKind::InvalidRequest,
+            Error::AuthenticationError(_) | Error::InvalidApiKey => ErrorKind::Authentication,
+            Error::PermissionDenied(_) => ErrorKind::PermissionDenied,
+            Error::NotFound(_) | Error::NoStoredFile { .. } => ErrorKind::NotFound,
+            Error::Conflict(_) | Error::ConcurrentModification { .. } => ErrorKind::Conflict,
+            Error::RateLimitExceeded(_) => ErrorKind::RateLimited,
+            Error::InternalServerError(_) => ErrorKind::Server,
+            Error::Api(_) => ErrorKind::Api,
+            Error::Json(_) | Error::Decode { .. } | Error::Base64(_) => ErrorKind::Serialization,
+            Error::InvalidConfig(_) => ErrorKind::Configuration,
+            Error::Io(_) => ErrorKind::Io,
+            Error::Cancelled => ErrorKind::Cancelled,
+            Error::DeadlineExceeded | Error::WaitTimeout(_) => ErrorKind::DeadlineExceeded,
+            Error::IndexingFailed { .. } => ErrorKind::IndexingFailed,
+            Error::ShutDown | Error::CircuitOpen | Error::WriteQueued => ErrorKind::NotSent,
+            Error::Request { source, .. } => source.kind(),
+            Error::RetryBudgetExhausted { last_error, .. } => last_error.kind(),
+        }
+    }
+
+    /// Attach the endpoint and attempt count of a call to a transport failure
+    pub(crate) fn in_request(self, endpoint: &str, attempts: u32) -> Self {
+        match self {
+            Error::Http(_) | Error::Transport(_) => Error::Request {
+                endpoint: endpoint.to_string(),
+                attempts,
+                source: Box::new(self),
+            },
+            Error::RetryBudgetExhausted { attempts, last_error } => Error::RetryBudgetExhausted {
+                attempts,
+                last_error: Box::new(last_error.in_request(endpoint, attempts)),
+            },
+            error => error,
+        }
+    }
+
+    /// Copy of this error for another caller sharing the same call
+    ///
+    /// Keeps the variant, and so [`kind`](Self::kind) and retryability;
+    /// sources that cannot be cloned are rebuilt from their messages.
+    pub(crate) fn duplicate(&self) -> Error {
+        match self {
+            // reqwest errors cannot be constructed, so keep the message
+            Error::Http(e) => Error::Transport(e.to_string().into()),
+            Error::Transport(e) => Error::Transport(match e.downcast_ref::<std::io::Error>() {
+                Some(io) => Box::new(std::io::Error::new(io.kind(), io.to_string())),
+                None => e.to_string().into(),
+            }),
+            Error::Request { endpoint, attempts, source } => Error::Request {
+                endpoint: endpoint.clone(),
+                attempts: *attempts,
+                source: Box::new(source.duplicate()),
+            },
+            Error::Api(e) => Error::Api(e.clone()),
+            Error::BadRequest(e) => Error::BadRequest(e.clone()),
+            Error::AuthenticationError(e) => Error::AuthenticationError(e.clone()),
+            Error::PermissionDenied(e) => Error::PermissionDenied(e.clone()),
+            Error::NotFound(e) => Error::NotFound(e.clone()),
+            Error::Conflict(e) => Error::Conflict(e.clone()),
+            Error::UnprocessableEntity(e) => Error::UnprocessableEntity(e.clone()),
+            Error::RateLimitExceeded(e) => Error::RateLimitExceeded(e.clone()),
+            Error::InternalServerError(e) => Error::InternalServerError(e.clone()),
+            Error::Json(e) => Error::Json(serde::de::Error::custom(e)),
+            Error::Decode { endpoint, body, source } => Error::Decode {
+                endpoint: endpoint.clone(),
+                body: body.clone(),
+                source: serde::de::Error::custom(source),
+            },
+            Error::InvalidApiKey => Error::InvalidApiKey,
+            Error::InvalidConfig(message) => Error::InvalidConfig(message.clone()),
+            Error::Io(e) => Error::Io(std::io::Error::new(e.kind(), e.to_string())),
+            Error::Base64(e) => Error::Base64(e.clone()),
+            Error::Cancelled => Error::Cancelled,
+            Error::DeadlineExceeded => Error::DeadlineExceeded,
+            Error::ShutDown => Error::ShutDown,
+            Error::CircuitOpen => Error::CircuitOpen,
+            Error::WriteQueued => Error::WriteQueued,
+            Error::IndexingFailed { path, status, reason } => Error::IndexingFailed {
+                path: path.clone(),
+                status: *status,
+                reason: reason.clone(),
+            },
+            Error::WaitTimeout(waited) => Error::WaitTimeout(*waited),
+            Error::ResourceTooLarge { url, limit } => Error::ResourceTooLarge {
+                url: url.clone(),
+                limit: *limit,
+            },
+            Error::UnsupportedContentType { url, content_type } => Error::UnsupportedContentType {
+                url: url.clone(),
+                content_type: content_type.clone(),
+            },
+            Error::ConcurrentModification { path } => {
+                Error::ConcurrentModification { path: path.clone() }
+            }
+            Error::NoStoredFile { path } => Error::NoStoredFile { path: path.clone() },
+            Error::InvalidParameter(message) => Error::InvalidParameter(message.clone()),
+            Error::RetryBudgetExhausted { attempts, last_error } => Error::RetryBudgetExhausted {
+                attempts: *attempts,
+                last_error: Box::new(last_error.duplicate()),
+            },
+        }
+    }
+
     /// Create an API error from response status and message
     pub fn from_status(status: u16, message: String) -> Self {
-        match status {
-            400 => Error::BadRequest(message),
-            401 => Error::AuthenticationError(message),
-            403 => Error::PermissionDenied(message),
-            404 => Error::NotFound(message),
-            409 => Error::Conflict(message),
-            422 => Error::UnprocessableEntity(message),
-            429 => Error::RateLimitExceeded(message),
-            500..=599 => Error::InternalServerError(message),
-            _ => Error::Api { status, message },
+        Self::from_api_error(ApiError::new(status, message))
+    }
+
+    /// Create the error variant matching an API error's status code
+    pub fn from_api_error(error: ApiError) -> Self {
+        match error.status {
+            400 => Error::BadRequest(error),
+            401 => Error::AuthenticationError(error),
+            403 => Error::PermissionDenied(error),
+            404 => Error::NotFound(error),
+            409 => Error::Conflict(error),
+            422 => Error::UnprocessableEntity(error),
+            429 => Error::RateLimitExceeded(error),
+            500..=599 => Error::InternalServerError(error),
+            _ => Error::Api(error),
         }
     }
+
+    /// Details of the API error response, if this error came from one
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Api(e)
+            | Error::BadRequest(e)
+            | Error::AuthenticationError(e)
+            | Error::PermissionDenied(e)
+            | Error::NotFound(e)
+            | Error::Conflict(e)
+            | Error::UnprocessableEntity(e)
+            | Error::RateLimitExceeded(e)
+            | Error::InternalServerError(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Request ID of the failed call, for correlating with server-side logs
+    pub fn request_id(&self) -> Option<&str> {
+        self.api_error().and_then(|e| e.request_id.as_deref())
+    }
+
+    /// HTTP status code of the API error response, if this error came from one
+    pub fn status_code(&self) -> Option<u16> {
+        self.api_error().map(|e| e.status)
+    }
+
+    /// Request fields rejected by validation, as `(field, message)` pairs
+    ///
+    /// Populated for [`Error::UnprocessableEntity`] responses that list the
+    /// failed validations; empty otherwise. See [`ValidationError::field`]
+    /// for the field format.
+    pub fn field_errors(&self) -> Vec<(String, String)> {
+        self.api_error()
+            .map(|e| {
+                e.validation_errors()
+                    .iter()
+                    .map(|v| (v.field(), v.msg.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// API endpoint of the failed call, if known
+    pub fn endpoint(&self) -> Option<&str> {
+        match self {
+            Error::Decode { endpoint, .. } | Error::Request { endpoint, .. } => Some(endpoint),
+            Error::RetryBudgetExhausted { last_error, .. } => last_error.endpoint(),
+            _ => self.api_error().and_then(ApiError::endpoint),
+        }
+    }
+
+    /// Raw body of the response, if this error came from an error response
+    /// or an undecodable success body
+    pub fn raw_body(&self) -> Option<&[u8]> {
+        match self {
+            Error::Decode { body, .. } => Some(body.as_bytes()),
+            _ => self.api_error().and_then(ApiError::raw_body),
+        }
+    }
+
+    /// Whether the API rejected the request for exceeding the rate limit (429)
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, Error::RateLimitExceeded(_))
+    }
+
+    /// Whether the requested resource does not exist (404)
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::NotFound(_))
+    }
+
+    /// Whether the failure is transient and the same request may succeed
+    /// when sent again
+    ///
+    /// True for responses with one of the [`DEFAULT_RETRYABLE_STATUSES`]
+    /// and for connection failures and timeouts. Useful for wrappers that
+    /// implement their own retries.
+    ///
+    /// [`DEFAULT_RETRYABLE_STATUSES`]: crate::DEFAULT_RETRYABLE_STATUSES
+    pub fn is_retryable(&self) -> bool {
+        match self.status_code() {
+            Some(status) => crate::client::DEFAULT_RETRYABLE_STATUSES.contains(&status),
+            None => self.is_connection_error(),
+        }
+    }
+
+    /// Whether a transport failure means the endpoint could not be reached
+    pub(crate) fn is_connection_error(&self) -> bool {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Error::Http(e) => e.is_connect() || e.is_timeout(),
+            // fetch failures on wasm32 carry no connect/timeout distinction
+            #[cfg(target_arch = "wasm32")]
+            Error::Http(e) => e.is_request(),
+            Error::Transport(_) => true,
+            Error::Request { source, .. } => source.is_connection_error(),
+            _ => false,
+        }
+    }
+
+    /// Whether the request failed to connect, so it never reached the API
+    ///
+    /// Unlike [`is_connection_error`](Self::is_connection_error) this
+    /// excludes timeouts, after which the API may already have acted on the
+    /// request. Custom transport errors count when caused by a refused
+    /// connection.
+    pub(crate) fn is_connect_error(&self) -> bool {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Error::Http(e) => e.is_connect(),
+            Error::Transport(e) => {
+                let mut source: Option<&(dyn std::error::Error + 'static)> = Some(e.as_ref());
+                while let Some(error) = source {
+                    if let Some(io) = error.downcast_ref::<std::io::Error>() {
+                        return matches!(
+                            io.kind(),
+                            std::io::ErrorKind::ConnectionRefused
+                                | std::io::ErrorKind::AddrNotAvailable
+                        );
+                    }
+                    source = error.source();
+                }
+                false
+            }
+            Error::Request { source, .. } => source.is_connect_error(),
+            _ => false,
+        }
+    }
+}
+
+/// An error response returned by the API
+///
+/// Displays as the error message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// HTTP status code
+    pub status: u16,
+    /// Error message from the response body
+    pub message: String,
+    /// Structured error payload, when the response body was JSON
+    pub body: Option<Box<ApiErrorBody>>,
+    /// ID of the request that failed (`X-Request-Id`)
+    pub request_id: Option<String>,
+    /// Delay the server asked for through `Retry-After` (429 and 503 only)
+    pub retry_after: Option<Duration>,
+    /// Where the failed request went and what came back, for debugging
+    context: Option<Box<RequestContext>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct RequestContext {
+    endpoint: Option<String>,
+    raw_body: Option<Vec<u8>>,
+}
+
+impl ApiError {
+    /// Create an API error from a status code and message
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            body: None,
+            request_id: None,
+            retry_after: None,
+            context: None,
+        }
+    }
+
+    /// Attach the structured error payload
+    pub fn with_body(mut self, body: ApiErrorBody) -> Self {
+        self.body = Some(Box::new(body));
+        self
+    }
+
+    /// Field-level validation failures reported with a 422 response
+    pub fn validation_errors(&self) -> &[ValidationError] {
+        match self.body.as_ref().and_then(|b| b.detail.as_ref()) {
+            Some(ErrorDetail::Validation(errors)) => errors,
+            _ => &[],
+        }
+    }
+
+    /// Attach the API endpoint the failed request was sent to
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.context.get_or_insert_with(Default::default).endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Attach the response body exactly as received
+    pub fn with_raw_body(mut self, raw_body: impl Into<Vec<u8>>) -> Self {
+        self.context.get_or_insert_with(Default::default).raw_body = Some(raw_body.into());
+        self
+    }
+
+    /// API endpoint the failed request was sent to, e.g. `/queries/top-documents`
+    pub fn endpoint(&self) -> Option<&str> {
+        self.context.as_ref()?.endpoint.as_deref()
+    }
+
+    /// Response body exactly as received (after decompression)
+    pub fn raw_body(&self) -> Option<&[u8]> {
+        self.context.as_ref()?.raw_body.as_deref()
+    }
+
+    /// Attach the ID of the request that failed
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Attach the delay the server asked for before retrying
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// JSON payload of an API error response
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    /// Human-readable error message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Machine-readable error code
+    #[serde(default, alias = "error_code", skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Error details: a description, or the failed validations of a 422
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<ErrorDetail>,
+}
+
+impl ApiErrorBody {
+    /// One-line summary of the payload, if it carries any description
+    pub(crate) fn summary(&self) -> Option<String> {
+        if let Some(message) = &self.message {
+            return Some(message.clone());
+        }
+        match self.detail.as_ref()? {
+            ErrorDetail::Message(message) => Some(message.clone()),
+            ErrorDetail::Validation(errors) if !errors.is_empty() => Some(
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// The `detail` field of an API error response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ErrorDetail {
+    /// Plain description of the error
+    Message(String),
+    /// Request fields that failed validation
+    Validation(Vec<ValidationError>),
+    /// Any other shape
+    Other(serde_json::Value),
+}
+
+/// A request field that failed validation
+///
+/// Displays as the dotted field location followed by the message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationError {
+    /// Path to the offending field, e.g. `["body", "k"]`
+    #[serde(default)]
+    pub loc: Vec<serde_json::Value>,
+    /// What is wrong with the field
+    pub msg: String,
+    /// Validation error type, e.g. `greater_than_equal`
+    #[serde(rename = "type", default)]
+    pub kind: String,
+}
+
+impl ValidationError {
+    /// Dotted path of the offending request field, e.g. `k` or
+    /// `filter.year`
+    ///
+    /// Drops the leading `body` segment of the location. Empty when the
+    /// error concerns the request as a whole.
+    pub fn field(&self) -> String {
+        let loc = match self.loc.first() {
+            Some(serde_json::Value::String(first)) if first == "body" => &self.loc[1..],
+            _ => &self.loc[..],
+        };
+        join_loc(loc)
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.loc.is_empty() {
+            f.write_str(&self.msg)
+        } else {
+            write!(f, "{}: {}", join_loc(&self.loc), self.msg)
+        }
+    }
+}
+
+/// Join location segments (names and array indices) with dots
+fn join_loc(loc: &[serde_json::Value]) -> String {
+    loc.iter()
+        .map(|part| match part {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
 }
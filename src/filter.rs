@@ -0,0 +1,144 @@
+//! Typed metadata filters
+//!
+//! [`F`] builds the metadata filters accepted by queries without spelling
+//! out the API's operator JSON by hand, so a misspelled operator is a
+//! compile error rather than a rejected request.
+//!
+//! # Example
+//! ```
+//! use zeroentropy_community::{Filter, F};
+//!
+//! let filter: Filter = F::field("specialty")
+//!     .eq("Cardiology")
+//!     .and(F::field("year").gte(2020))
+//!     .into();
+//!
+//! assert_eq!(
+//!     serde_json::to_value(&filter).unwrap(),
+//!     serde_json::json!({
+//!         "$and": [
+//!             { "specialty": { "$eq": "Cardiology" } },
+//!             { "year": { "$gte": 2020 } },
+//!         ]
+//!     }),
+//! );
+//! ```
+
+use crate::types::Filter;
+use serde_json::Value;
+
+/// Entry point of the filter DSL
+pub struct F;
+
+impl F {
+    /// Start a condition on the metadata field `name`
+    pub fn field(name: impl Into<String>) -> Field {
+        Field { name: name.into() }
+    }
+}
+
+/// A metadata field awaiting its comparison, created by [`F::field`]
+#[derive(Debug, Clone)]
+pub struct Field {
+    name: String,
+}
+
+impl Field {
+    /// Match documents whose field equals `value`
+    pub fn eq(self, value: impl Into<Value>) -> FilterBuilder {
+        self.condition("$eq", value.into())
+    }
+
+    /// Match documents whose field differs from `value`
+    pub fn ne(self, value: impl Into<Value>) -> FilterBuilder {
+        self.condition("$ne", value.into())
+    }
+
+    /// Match documents whose field is greater than `value`
+    pub fn gt(self, value: impl Into<Value>) -> FilterBuilder {
+        self.condition("$gt", value.into())
+    }
+
+    /// Match documents whose field is greater than or equal to `value`
+    pub fn gte(self, value: impl Into<Value>) -> FilterBuilder {
+        self.condition("$gte", value.into())
+    }
+
+    /// Match documents whose field is less than `value`
+    pub fn lt(self, value: impl Into<Value>) -> FilterBuilder {
+        self.condition("$lt", value.into())
+    }
+
+    /// Match documents whose field is less than or equal to `value`
+    pub fn lte(self, value: impl Into<Value>) -> FilterBuilder {
+        self.condition("$lte", value.into())
+    }
+
+    /// Match documents whose field is one of `values`
+    pub fn is_in<V: Into<Value>>(self, values: impl IntoIterator<Item = V>) -> FilterBuilder {
+        self.condition("$in", values.into_iter().map(Into::into).collect())
+    }
+
+    /// Match documents whose field is none of `values`
+    pub fn not_in<V: Into<Value>>(self, values: impl IntoIterator<Item = V>) -> FilterBuilder {
+        self.condition("$nin", values.into_iter().map(Into::into).collect())
+    }
+
+    fn condition(self, operator: &str, value: Value) -> FilterBuilder {
+        let mut condition = serde_json::Map::new();
+        condition.insert(operator.to_string(), value);
+        FilterBuilder {
+            filter: Filter::from([(self.name, Value::Object(condition))]),
+        }
+    }
+}
+
+/// A metadata filter built with [`F`]
+///
+/// Converts into a [`Filter`] wherever one is accepted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterBuilder {
+    filter: Filter,
+}
+
+impl FilterBuilder {
+    /// Match documents that also match `other`
+    pub fn and(self, other: FilterBuilder) -> Self {
+        self.combine("$and", other)
+    }
+
+    /// Match documents that match either this filter or `other`
+    pub fn or(self, other: FilterBuilder) -> Self {
+        self.combine("$or", other)
+    }
+
+    /// Finish the filter
+    pub fn build(self) -> Filter {
+        self.filter
+    }
+
+    /// Join with `other` under `operator`, appending to an existing list of
+    /// the same operator so chained calls stay flat
+    fn combine(mut self, operator: &str, other: FilterBuilder) -> Self {
+        if self.filter.len() == 1 {
+            if let Some(Value::Array(operands)) = self.filter.get_mut(operator) {
+                operands.push(other.into_value());
+                return self;
+            }
+        }
+        let operands = vec![self.into_value(), other.into_value()];
+        FilterBuilder {
+            filter: Filter::from([(operator.to_string(), Value::Array(operands))]),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Object(self.filter.into_iter().collect())
+    }
+}
+
+impl From<FilterBuilder> for Filter {
+    fn from(builder: FilterBuilder) -> Self {
+        builder.build()
+    }
+}
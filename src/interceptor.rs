@@ -0,0 +1,84 @@
+//! Request/response interceptors
+//!
+//! Interceptors observe every HTTP exchange made by a [`Client`](crate::Client)
+//! and may modify outgoing requests, which makes them the place for central
+//! logging, header injection and metrics collection.
+//!
+//! # Example
+//! ```
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use zeroentropy_community::{Client, HttpRequest, HttpResponse, Interceptor};
+//!
+//! #[derive(Default)]
+//! struct Metrics {
+//!     failures: AtomicU64,
+//! }
+//!
+//! impl Interceptor for Metrics {
+//!     fn on_request(&self, request: &mut HttpRequest) {
+//!         request.headers.insert("x-trace-id", "abc123".parse().unwrap());
+//!     }
+//!
+//!     fn on_response(&self, _request: &HttpRequest, response: &HttpResponse) {
+//!         if response.status >= 400 {
+//!             self.failures.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     }
+//! }
+//!
+//! let client = Client::builder()
+//!     .api_key("test-key")
+//!     .interceptor(Metrics::default())
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::error::Error;
+use crate::transport::{HttpRequest, HttpResponse};
+use std::time::Duration;
+
+/// Details about a retry that is about to happen
+#[derive(Debug)]
+pub struct RetryEvent<'a> {
+    /// The request being retried
+    pub request: &'a HttpRequest,
+    /// Retry number, starting at 1, counting failovers to another base URL
+    pub attempt: u32,
+    /// How long the client waits before retrying, zero for a failover
+    pub delay: Duration,
+    /// Status code of the response that triggered the retry
+    pub status: Option<u16>,
+    /// Error of the failed attempt
+    pub error: &'a Error,
+}
+
+/// Hooks invoked around every HTTP exchange
+///
+/// All methods have empty default implementations, so implementors only
+/// override the hooks they need. Hooks run in registration order.
+pub trait Interceptor: Send + Sync {
+    /// Called before each attempt is sent; may modify the request
+    fn on_request(&self, _request: &mut HttpRequest) {}
+
+    /// Called for every response received, including error statuses
+    fn on_response(&self, _request: &HttpRequest, _response: &HttpResponse) {}
+
+    /// Called when the transport fails without producing a response
+    fn on_error(&self, _request: &HttpRequest, _error: &Error) {}
+
+    /// Called before the client waits to retry a request
+    fn on_retry(&self, _event: &RetryEvent<'_>) {}
+}
+
+/// Adapts a closure passed to
+/// [`ClientBuilder::on_retry`](crate::ClientBuilder::on_retry)
+pub(crate) struct OnRetry<F>(pub(crate) F);
+
+impl<F> Interceptor for OnRetry<F>
+where
+    F: Fn(&RetryEvent<'_>) + Send + Sync,
+{
+    fn on_retry(&self, event: &RetryEvent<'_>) {
+        (self.0)(event)
+    }
+}
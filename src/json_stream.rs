@@ -0,0 +1,206 @@
+//! Incremental extraction of a string field from a JSON response
+//!
+//! Lets large document content be written out as the response arrives,
+//! without holding the whole body or the decoded string in memory.
+
+use crate::error::{Error, Result};
+
+/// Streams the value of the string field `key` found at `depth` (1 being
+/// the members of the top-level object), unescaping it on the fly
+pub(crate) struct StringFieldExtractor {
+    key: &'static [u8],
+    depth: usize,
+    /// Open containers; `true` for objects
+    stack: Vec<bool>,
+    /// Whether the innermost object expects a key next
+    expect_key: bool,
+    /// Last key read in the innermost object
+    last_key: Vec<u8>,
+    state: State,
+    found: bool,
+}
+
+enum State {
+    /// Between tokens
+    Structure,
+    /// Inside a key or a value we skip; `true` right after a backslash
+    String { is_key: bool, escaped: bool },
+    /// Inside the target value
+    Target(Escape),
+    /// Target fully read; the rest of the input is ignored
+    Done,
+}
+
+enum Escape {
+    None,
+    Backslash,
+    /// Reading the hex digits of `\uXXXX`
+    Unicode { digits: u8, code: u32 },
+    /// A high surrogate was read and must be followed by `\u` + low surrogate
+    Surrogate { high: u32, seen: u8 },
+}
+
+impl StringFieldExtractor {
+    pub(crate) fn new(key: &'static str, depth: usize) -> Self {
+        Self {
+            key: key.as_bytes(),
+            depth,
+            stack: Vec::new(),
+            expect_key: false,
+            last_key: Vec::new(),
+            state: State::Structure,
+            found: false,
+        }
+    }
+
+    /// Whether the target value has been read completely
+    pub(crate) fn is_done(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+
+    /// Check that the target value was present and read to its end
+    pub(crate) fn finish(&self) -> Result<()> {
+        match (&self.state, self.found) {
+            (State::Done, _) => Ok(()),
+            (_, true) => Err(invalid("response ended inside the string")),
+            (_, false) => Err(invalid("response has no such string field")),
+        }
+    }
+
+    /// Feed the next chunk of the response, appending unescaped target
+    /// bytes to `out`
+    pub(crate) fn feed(&mut self, chunk: &[u8], out: &mut Vec<u8>) -> Result<()> {
+        for &byte in chunk {
+            match &mut self.state {
+                State::Done => break,
+                State::Structure => self.structure(byte),
+                State::String { is_key, escaped } => {
+                    if *escaped {
+                        *escaped = false;
+                    } else if byte == b'\\' {
+                        *escaped = true;
+                    } else if byte == b'"' {
+                        if *is_key {
+                            self.expect_key = false;
+                        }
+                        self.state = State::Structure;
+                    } else if *is_key {
+                        self.last_key.push(byte);
+                    }
+                }
+                State::Target(escape) => match escape {
+                    Escape::None => match byte {
+                        b'"' => self.state = State::Done,
+                        b'\\' => *escape = Escape::Backslash,
+                        _ => out.push(byte),
+                    },
+                    Escape::Backslash => {
+                        let unescaped = match byte {
+                            b'"' | b'\\' | b'/' => byte,
+                            b'b' => 0x08,
+                            b'f' => 0x0c,
+                            b'n' => b'\n',
+                            b'r' => b'\r',
+                            b't' => b'\t',
+                            b'u' => {
+                                *escape = Escape::Unicode { digits: 0, code: 0 };
+                                continue;
+                            }
+                            _ => return Err(invalid("invalid escape in string")),
+                        };
+                        out.push(unescaped);
+                        *escape = Escape::None;
+                    }
+                    Escape::Unicode { digits, code } => {
+                        let digit = (byte as char)
+                            .to_digit(16)
+                            .ok_or_else(|| invalid("invalid unicode escape"))?;
+                        *code = *code * 16 + digit;
+                        *digits += 1;
+                        if *digits == 4 {
+                            let code = *code;
+                            *escape = match code {
+                                0xD800..=0xDBFF => Escape::Surrogate { high: code, seen: 0 },
+                                0xDC00..=0xDFFF => return Err(invalid("unpaired surrogate")),
+                                _ => {
+                                    push_char(out, code)?;
+                                    Escape::None
+                                }
+                            };
+                        }
+                    }
+                    Escape::Surrogate { high, seen } => {
+                        // Expect `\u` followed by four hex digits of the low half
+                        match (*seen, byte) {
+                            (0, b'\\') | (1, b'u') => *seen += 1,
+                            (2..=5, _) => {
+                                let digit = (byte as char)
+                                    .to_digit(16)
+                                    .ok_or_else(|| invalid("invalid unicode escape"))?;
+                                *high = (*high << 4) | digit;
+                                *seen += 1;
+                                if *seen == 6 {
+                                    let low = *high & 0xFFFF;
+                                    let high = *high >> 16;
+                                    if !(0xDC00..=0xDFFF).contains(&low) {
+                                        return Err(invalid("unpaired surrogate"));
+                                    }
+                                    let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                                    push_char(out, code)?;
+                                    *escape = Escape::None;
+                                }
+                            }
+                            _ => return Err(invalid("unpaired surrogate")),
+                        }
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn structure(&mut self, byte: u8) {
+        match byte {
+            b'{' => {
+                self.stack.push(true);
+                self.expect_key = true;
+            }
+            b'[' => {
+                self.stack.push(false);
+                self.expect_key = false;
+            }
+            b'}' | b']' => {
+                self.stack.pop();
+                self.expect_key = false;
+            }
+            b',' => self.expect_key = self.stack.last() == Some(&true),
+            b'"' if self.expect_key => {
+                self.last_key.clear();
+                self.state = State::String { is_key: true, escaped: false };
+            }
+            b'"' => {
+                let is_target = self.stack.len() == self.depth
+                    && self.stack.last() == Some(&true)
+                    && self.last_key == self.key;
+                if is_target {
+                    self.found = true;
+                    self.state = State::Target(Escape::None);
+                } else {
+                    self.state = State::String { is_key: false, escaped: false };
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_char(out: &mut Vec<u8>, code: u32) -> Result<()> {
+    let c = char::from_u32(code).ok_or_else(|| invalid("invalid unicode escape"))?;
+    let mut buf = [0; 4];
+    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    Ok(())
+}
+
+fn invalid(message: &str) -> Error {
+    Error::Json(<serde_json::Error as serde::de::Error>::custom(message))
+}
@@ -39,34 +39,124 @@
 //! }
 //! ```
 
+mod auth;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+mod cache;
+mod circuit_breaker;
 mod client;
 mod error;
+mod filter;
+mod interceptor;
+#[cfg(not(target_arch = "wasm32"))]
+mod json_stream;
+#[cfg(not(target_arch = "wasm32"))]
+mod profile;
+mod rate_limit;
 mod resources;
+mod response;
+mod retry;
+mod runtime;
+#[cfg(all(feature = "tower", not(target_arch = "wasm32")))]
+mod service;
+mod shutdown;
+mod stats;
+mod transport;
 mod types;
+#[cfg(not(target_arch = "wasm32"))]
+mod write_queue;
 
-pub use client::{Client, ClientBuilder};
-pub use error::{Error, Result};
-pub use resources::{Collections, Documents, Models, Queries};
+pub use auth::ApiKeyProvider;
+pub use cache::{CacheBackend, InMemoryCache};
+pub use client::{
+    Client, ClientBuilder, CompressionStats, RequestCompression, RequestOptions,
+    DEFAULT_API_VERSION, DEFAULT_RETRYABLE_STATUSES,
+};
+pub use error::{
+    ApiError, ApiErrorBody, Error, ErrorDetail, ErrorKind, Result, ValidationError,
+};
+pub use filter::{Field, FilterBuilder, F};
+pub use interceptor::{Interceptor, RetryEvent};
+pub use transport::{
+    async_trait, HttpRequest, HttpResponse, HttpTransport, ReqwestTransport,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use transport::{BodyStream, HttpStreamResponse};
+#[cfg(feature = "middleware")]
+pub use transport::MiddlewareTransport;
+pub use resources::{
+    Admin, Collections, Documents, Models, Parsers, Queries, Search, TypedCollection,
+    DEFAULT_BATCH_CONCURRENCY, DEFAULT_MAX_DOWNLOAD_SIZE, DEFAULT_RERANK_CHUNK_SIZE,
+    INDEX_POLL_INTERVAL, MAX_PAGE_RANGE, MAX_TOP_DOCUMENTS_K, MAX_TOP_PAGES_K, MAX_TOP_SNIPPETS_K,
+};
+pub use response::{ApiResponse, RateLimitStatus};
+pub use retry::{ConstantBackoff, ExponentialBackoff, Jitter, NoRetry, RetryPolicy};
+pub use shutdown::ShutdownReport;
+pub use stats::ClientStats;
+#[cfg(all(feature = "tower", not(target_arch = "wasm32")))]
+pub use service::ZeroEntropyRequest;
 pub use types::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use write_queue::{ConflictStrategy, FlushReport, QueuedWrite};
+pub use tokio_util::sync::CancellationToken;
 
 impl Client {
     /// Access the collections resource
-    pub fn collections(&self) -> Collections {
+    pub fn collections(&self) -> Collections<'_> {
         Collections::new(self)
     }
 
     /// Access the documents resource
-    pub fn documents(&self) -> Documents {
+    pub fn documents(&self) -> Documents<'_> {
         Documents::new(self)
     }
 
     /// Access the queries resource
-    pub fn queries(&self) -> Queries {
+    pub fn queries(&self) -> Queries<'_> {
         Queries::new(self)
     }
 
+    /// Start a fluent search of `collection_name`
+    ///
+    /// Shorthand for `client.queries().search(collection_name)`; see
+    /// [`Search`].
+    pub fn search(&self, collection_name: impl Into<String>) -> Search<'_> {
+        self.queries().search(collection_name)
+    }
+
     /// Access the models resource
-    pub fn models(&self) -> Models {
+    pub fn models(&self) -> Models<'_> {
         Models::new(self)
     }
+
+    /// Access the admin resource
+    pub fn admin(&self) -> Admin<'_> {
+        Admin::new(self)
+    }
+
+    /// Access the parsers resource
+    pub fn parsers(&self) -> Parsers<'_> {
+        Parsers::new(self)
+    }
+
+    /// Access a collection whose document metadata follows the schema `M`
+    pub fn typed_collection<M>(&self, name: impl Into<String>) -> TypedCollection<'_, M>
+    where
+        M: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        TypedCollection::new(self, name)
+    }
+
+    /// Access the default collection set with
+    /// [`ClientBuilder::default_collection`]
+    ///
+    /// Fails with [`Error::InvalidConfig`] when no default was configured.
+    pub fn collection(&self) -> Result<TypedCollection<'_, Metadata>> {
+        match self.default_collection_name() {
+            Some(name) => Ok(TypedCollection::new(self, name)),
+            None => Err(Error::InvalidConfig(
+                "no default collection configured".to_string(),
+            )),
+        }
+    }
 }
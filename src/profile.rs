@@ -0,0 +1,111 @@
+//! Named client profiles loaded from a config file
+//!
+//! Profiles live in `~/.zeroentropy/config.toml`, one table per profile:
+//!
+//! ```toml
+//! [default]
+//! api_key = "ze_dev_key"
+//!
+//! [prod]
+//! api_key = "ze_prod_key"
+//! base_url = "https://api.zeroentropy.dev/v1"
+//! timeout_secs = 120
+//! max_retries = 5
+//! project = "production"
+//! ```
+//!
+//! Settings missing from a profile fall back to the same defaults and
+//! environment variables as [`ClientBuilder::build`].
+
+use crate::client::{Client, ClientBuilder};
+use crate::error::{Error, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Settings of a single profile
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    timeout_secs: Option<u64>,
+    max_retries: Option<u32>,
+    organization: Option<String>,
+    project: Option<String>,
+}
+
+impl Profile {
+    fn apply(self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(api_key) = self.api_key {
+            builder = builder.api_key(api_key);
+        }
+        if let Some(base_url) = self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(timeout_secs) = self.timeout_secs {
+            builder = builder.timeout(Duration::from_secs(timeout_secs));
+        }
+        if let Some(max_retries) = self.max_retries {
+            builder = builder.max_retries(max_retries);
+        }
+        if let Some(organization) = self.organization {
+            builder = builder.organization(organization);
+        }
+        if let Some(project) = self.project {
+            builder = builder.project(project);
+        }
+        builder
+    }
+}
+
+/// Default location of the config file
+fn default_config_path() -> Result<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| Path::new(&home).join(".zeroentropy").join("config.toml"))
+        .ok_or_else(|| Error::InvalidConfig("cannot locate home directory".to_string()))
+}
+
+fn load_profile(path: &Path, name: &str) -> Result<Profile> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut profiles: HashMap<String, Profile> = toml::from_str(&contents)
+        .map_err(|e| Error::InvalidConfig(format!("invalid config file {}: {}", path.display(), e)))?;
+    profiles.remove(name).ok_or_else(|| {
+        Error::InvalidConfig(format!("profile '{}' not found in {}", name, path.display()))
+    })
+}
+
+impl Client {
+    /// Create a new client from a named profile in `~/.zeroentropy/config.toml`
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zeroentropy_community::Client;
+    ///
+    /// let client = Client::from_profile("prod").unwrap();
+    /// ```
+    pub fn from_profile(name: &str) -> Result<Self> {
+        Self::from_profile_file(default_config_path()?, name)
+    }
+
+    /// Create a new client from a named profile in the given config file
+    pub fn from_profile_file(path: impl AsRef<Path>, name: &str) -> Result<Self> {
+        ClientBuilder::default().profile_file(path, name)?.build()
+    }
+}
+
+impl ClientBuilder {
+    /// Apply a named profile from `~/.zeroentropy/config.toml`
+    ///
+    /// Builder methods called afterwards override the profile's settings.
+    pub fn profile(self, name: &str) -> Result<Self> {
+        self.profile_file(default_config_path()?, name)
+    }
+
+    /// Apply a named profile from the given config file
+    pub fn profile_file(self, path: impl AsRef<Path>, name: &str) -> Result<Self> {
+        Ok(load_profile(path.as_ref(), name)?.apply(self))
+    }
+}
@@ -0,0 +1,55 @@
+//! Client-side token-bucket rate limiting
+
+use crate::runtime::{self, Instant};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Token bucket shared by all clones of a client
+///
+/// Holds up to `burst` tokens, refilled at `rate` tokens per second. Each
+/// request takes one token; when none is left the caller reserves the next
+/// one and sleeps until it has been refilled, so waiters are served in
+/// arrival order.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    state: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens; negative while callers hold reservations
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: f64, burst: u32) -> Self {
+        Self {
+            rate: requests_per_second,
+            burst: f64::from(burst),
+            state: Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a request may be sent
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+            bucket.last_refill = now;
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        };
+        runtime::sleep(wait).await;
+    }
+}
@@ -0,0 +1,80 @@
+use crate::client::{Client, RequestOptions};
+use crate::error::Result;
+use crate::types::{ApiKeyListResponse, ApiKeyResponse, CreateApiKeyResponse};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Admin resource for managing the organization's API keys
+///
+/// Calls must be made with a key that has admin access.
+pub struct Admin<'a> {
+    client: &'a Client,
+    options: RequestOptions,
+}
+
+impl<'a> Admin<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Create an API key
+    ///
+    /// The key itself is only ever returned here; store it right away.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let created = client.admin().create_api_key("ingest-worker").await?;
+    /// println!("created {}", created.api_key_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_api_key(&self, name: impl Into<String>) -> Result<CreateApiKeyResponse> {
+        #[derive(Serialize)]
+        struct Request {
+            name: String,
+        }
+
+        let body = Request { name: name.into() };
+
+        self.client.post("/admin/create-api-key", &body, &self.options).await
+    }
+
+    /// List the organization's API keys
+    pub async fn list_api_keys(&self) -> Result<ApiKeyListResponse> {
+        self.client
+            .post("/admin/get-api-key-list", &serde_json::json!({}), &self.options)
+            .await
+    }
+
+    /// Revoke an API key so it can no longer be used
+    pub async fn revoke_api_key(&self, api_key_id: impl Into<String>) -> Result<ApiKeyResponse> {
+        #[derive(Serialize)]
+        struct Request {
+            api_key_id: String,
+        }
+
+        let body = Request {
+            api_key_id: api_key_id.into(),
+        };
+
+        self.client.post("/admin/revoke-api-key", &body, &self.options).await
+    }
+}
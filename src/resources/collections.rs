@@ -1,16 +1,41 @@
-use crate::client::Client;
-use crate::error::Result;
-use crate::types::{CollectionListResponse, CollectionResponse};
+use super::documents::{Documents, DEFAULT_BATCH_CONCURRENCY};
+use crate::client::{Client, RequestOptions};
+use crate::error::{Error, Result};
+use crate::runtime::{self, Instant};
+use crate::types::{
+    CollectionIndexReport, CollectionListResponse, CollectionResponse, CopyOptions, CopyReport,
+    DocumentInfo, Metadata,
+};
 use serde::Serialize;
+use std::time::Duration;
+
+/// How often [`Collections::wait_until_indexed`] checks the index status
+pub const INDEX_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Collections resource for managing document collections
 pub struct Collections<'a> {
     client: &'a Client,
+    options: RequestOptions,
 }
 
 impl<'a> Collections<'a> {
     pub(crate) fn new(client: &'a Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
     }
 
     /// Add a new collection
@@ -37,7 +62,190 @@ impl<'a> Collections<'a> {
             collection_name: collection_name.into(),
         };
 
-        self.client.post("/collections/add-collection", &body).await
+        self.client.post("/collections/add-collection", &body, &self.options).await
+    }
+
+    /// Create a collection unless it already exists
+    ///
+    /// Returns whether the collection was created; a collection that
+    /// already exists is left as is.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// if client.collections().ensure("my_collection").await? {
+    ///     println!("created my_collection");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ensure(&self, collection_name: impl Into<String>) -> Result<bool> {
+        match self.add(collection_name).await {
+            Ok(_) => Ok(true),
+            Err(Error::Conflict(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wait until no document of a collection is waiting to be parsed or
+    /// indexed
+    ///
+    /// Polls the collection's [`index_report`](Documents::index_report)
+    /// every [`INDEX_POLL_INTERVAL`] and returns the last report once it
+    /// [`is_settled`](CollectionIndexReport::is_settled). Documents that
+    /// failed do not fail the wait; check [`CollectionIndexReport::failed`].
+    /// Fails with [`Error::WaitTimeout`] if the collection has not settled
+    /// within `timeout`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let report = client
+    ///     .collections()
+    ///     .wait_until_indexed("my_collection", Duration::from_secs(600))
+    ///     .await?;
+    /// println!("{} indexed, {} failed", report.indexed, report.failed());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_indexed(
+        &self,
+        collection_name: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<CollectionIndexReport> {
+        self.wait_until_indexed_with_progress(collection_name, timeout, |_| {})
+            .await
+    }
+
+    /// Wait until a collection is indexed, passing each polled report to
+    /// `on_progress`
+    ///
+    /// See [`wait_until_indexed`](Self::wait_until_indexed).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// client
+    ///     .collections()
+    ///     .wait_until_indexed_with_progress("my_collection", Duration::from_secs(600), |report| {
+    ///         println!("{}/{} indexed", report.indexed, report.total());
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_indexed_with_progress(
+        &self,
+        collection_name: impl Into<String>,
+        timeout: Duration,
+        mut on_progress: impl FnMut(&CollectionIndexReport),
+    ) -> Result<CollectionIndexReport> {
+        let collection_name = collection_name.into();
+        // Polling must see the live status, never a cached response
+        let mut options = self.options.clone();
+        options.refresh_cache = true;
+        let documents = Documents::new(self.client).with_options(options);
+
+        let started = Instant::now();
+        loop {
+            let report = documents.index_report(collection_name.clone()).await?;
+            on_progress(&report);
+            if report.is_settled() {
+                return Ok(report);
+            }
+            if started.elapsed() + INDEX_POLL_INTERVAL > timeout {
+                return Err(Error::WaitTimeout(timeout));
+            }
+            runtime::sleep(INDEX_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Copy every document of a collection, with its metadata and content,
+    /// into another collection
+    ///
+    /// The destination is created if missing. Documents are copied in path
+    /// order, [`CopyOptions::concurrency`] at a time; a document that fails
+    /// is reported in [`CopyReport::failed`] without stopping the copy.
+    /// Documents the destination already has are skipped, without
+    /// downloading their content, unless [`CopyOptions::overwrite`] is set,
+    /// so running a copy again picks up where it stopped. Metadata is copied
+    /// as is, without the client's
+    /// [default metadata](crate::ClientBuilder::default_metadata). For large collections, pass the
+    /// [`CopyReport::last_path`] of an interrupted run to
+    /// [`CopyOptions::resume_after`] to avoid revisiting copied documents.
+    ///
+    /// Fails only when listing the source collection fails.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, CopyOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let report = client
+    ///     .collections()
+    ///     .copy("staging", "prod", CopyOptions::new().overwrite(true))
+    ///     .await?;
+    /// println!("copied {}, {} failed", report.copied, report.failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy(
+        &self,
+        source: impl Into<String>,
+        destination: impl Into<String>,
+        options: CopyOptions,
+    ) -> Result<CopyReport> {
+        use futures_util::stream::{self, StreamExt, TryStreamExt};
+
+        let source = source.into();
+        let destination = destination.into();
+        self.ensure(destination.clone()).await?;
+
+        // Each write gets its own idempotency key
+        let mut request_options = self.options.clone();
+        request_options.idempotency_key = None;
+        let documents = Documents::new(self.client).with_options(request_options);
+
+        let mut report = CopyReport {
+            last_path: options.resume_after.clone(),
+            ..Default::default()
+        };
+        let pages = documents.list_pages::<Metadata>(source.clone(), options.resume_after.clone());
+        let mut pages = std::pin::pin!(pages);
+        while let Some(page) = pages.try_next().await? {
+            let last_path = page.last().map(|document| document.path.clone());
+
+            let outcomes: Vec<(String, Result<bool>)> = stream::iter(page)
+                .map(|document| {
+                    let path = document.path.clone();
+                    let outcome =
+                        copy_document(&documents, &source, &destination, document, &options);
+                    async move { (path, outcome.await) }
+                })
+                .buffered(options.concurrency)
+                .collect()
+                .await;
+            for (path, outcome) in outcomes {
+                match outcome {
+                    Ok(true) => report.copied += 1,
+                    Ok(false) | Err(Error::Conflict(_)) => report.skipped += 1,
+                    Err(e) => report.failed.push((path, e)),
+                }
+            }
+
+            if let Some(path) = last_path {
+                report.last_path = Some(path);
+            }
+        }
+        Ok(report)
     }
 
     /// Delete a collection
@@ -64,7 +272,7 @@ impl<'a> Collections<'a> {
             collection_name: collection_name.into(),
         };
 
-        self.client.post("/collections/delete-collection", &body).await
+        self.client.post("/collections/delete-collection", &body, &self.options).await
     }
 
     /// Get list of all collections
@@ -82,6 +290,85 @@ impl<'a> Collections<'a> {
     /// # }
     /// ```
     pub async fn get_list(&self) -> Result<CollectionListResponse> {
-        self.client.post("/collections/get-collection-list", &serde_json::json!({})).await
+        self.client
+            .post("/collections/get-collection-list", &serde_json::json!({}), &self.options)
+            .await
+    }
+
+    /// Get every collection with its document count in each index status
+    ///
+    /// The API has no endpoint returning statistics for all collections, so
+    /// this lists the collections, then builds an
+    /// [`index_report`](Documents::index_report) for each,
+    /// [`DEFAULT_BATCH_CONCURRENCY`] at a time. Reports come back in the
+    /// order of [`get_list`](Self::get_list). A collection deleted between
+    /// the two steps is left out.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// for stats in client.collections().get_list_with_stats().await? {
+    ///     println!(
+    ///         "{}: {} documents, {} indexed, {} failed",
+    ///         stats.collection_name,
+    ///         stats.total(),
+    ///         stats.indexed,
+    ///         stats.failed(),
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_list_with_stats(&self) -> Result<Vec<CollectionIndexReport>> {
+        use futures_util::stream::{self, StreamExt, TryStreamExt};
+
+        let names = self.get_list().await?.collections;
+        let documents = Documents::new(self.client).with_options(self.options.clone());
+        let reports: Vec<Option<CollectionIndexReport>> = stream::iter(names)
+            .map(|name| {
+                let documents = &documents;
+                async move {
+                    match documents.index_report(name).await {
+                        Ok(report) => Ok(Some(report)),
+                        Err(e) if e.is_not_found() => Ok(None),
+                        Err(e) => Err(e),
+                    }
+                }
+            })
+            .buffered(DEFAULT_BATCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+        Ok(reports.into_iter().flatten().collect())
+    }
+}
+
+/// Copy one document with its metadata as is, using its original file when
+/// the API does not return its content
+///
+/// Returns `false` when the document was skipped because the destination
+/// already has it.
+async fn copy_document(
+    documents: &Documents<'_>,
+    source: &str,
+    destination: &str,
+    document: DocumentInfo<Metadata>,
+    options: &CopyOptions,
+) -> Result<bool> {
+    // Skip without downloading the content, e.g. when re-running a copy
+    if !options.overwrite && documents.exists(destination, &document.path).await? {
+        return Ok(false);
     }
+    let content = documents.original_content(source, &document.path).await?;
+    documents
+        .add_exact(
+            destination.to_string(),
+            document.path,
+            content,
+            document.metadata,
+            Some(options.overwrite),
+        )
+        .await?;
+    Ok(true)
 }
@@ -1,19 +1,77 @@
-use crate::client::Client;
-use crate::error::Result;
+use crate::client::{Client, RequestOptions};
+use crate::error::{Error, Result};
+use crate::runtime::{self, Instant};
 use crate::types::{
-    DocumentContent, DocumentInfoListResponse, DocumentInfoResponse, DocumentResponse,
-    IndexStatus, Metadata, PageInfoResponse,
+    BatchAddResult, CollectionIndexReport, DeleteAllReport, DocumentContent, DocumentSpec,
+    DocumentInfoListResponse, DocumentInfoResponse, DocumentInfo, DocumentResponse, IndexStatus,
+    Filter, Metadata, PageInfo, PageInfoResponse, ReindexReport,
 };
-use serde::Serialize;
+use futures_util::stream::{self, Stream, TryStreamExt};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// Documents requested per page when walking a whole collection
+const LIST_PAGE_SIZE: u32 = 1024;
+/// Documents added at once by [`Documents::add_batch`]
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+/// Most pages [`Documents::get_pages`] fetches in one call
+pub const MAX_PAGE_RANGE: u32 = 256;
+/// Largest download accepted by [`Documents::add_url`], in bytes
+pub const DEFAULT_MAX_DOWNLOAD_SIZE: u64 = 100 * 1024 * 1024;
 
 /// Documents resource for managing documents in collections
 pub struct Documents<'a> {
     client: &'a Client,
+    options: RequestOptions,
 }
 
 impl<'a> Documents<'a> {
     pub(crate) fn new(client: &'a Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Options for a write call, with an idempotency key generated if the
+    /// caller didn't supply one
+    ///
+    /// The key is fixed before the first attempt so retries of the same
+    /// write share it and the server can discard duplicates.
+    fn write_options(&self) -> RequestOptions {
+        let mut options = self.options.clone();
+        if options.idempotency_key.is_none() {
+            options.idempotency_key = Some(uuid::Uuid::new_v4().to_string());
+        }
+        options
+    }
+
+    /// This resource for one of several writes made by a single call, with
+    /// an idempotency key set through [`with_options`](Self::with_options)
+    /// suffixed with `-{suffix}` so every write keeps a distinct key
+    fn with_key_suffix(&self, suffix: impl std::fmt::Display) -> Documents<'a> {
+        let mut options = self.options.clone();
+        options.idempotency_key = options
+            .idempotency_key
+            .map(|key| format!("{}-{}", key, suffix));
+        Documents {
+            client: self.client,
+            options,
+        }
     }
 
     /// Add a document to a collection
@@ -31,27 +89,125 @@ impl<'a> Documents<'a> {
         content: DocumentContent,
         metadata: Option<Metadata>,
         overwrite: Option<bool>,
+    ) -> Result<DocumentResponse> {
+        self.add_typed(collection_name.into(), path.into(), content, metadata, overwrite)
+            .await
+    }
+
+    pub(crate) async fn add_typed<M: Serialize>(
+        &self,
+        collection_name: String,
+        path: String,
+        content: DocumentContent,
+        metadata: Option<M>,
+        overwrite: Option<bool>,
+    ) -> Result<DocumentResponse> {
+        let metadata = self.client.with_default_metadata(metadata)?;
+        self.add_exact(collection_name, path, content, metadata, overwrite)
+            .await
+    }
+
+    /// Add a document with exactly `metadata`, without the client's default
+    /// metadata entries
+    pub(crate) async fn add_exact<M: Serialize>(
+        &self,
+        collection_name: String,
+        path: String,
+        content: DocumentContent,
+        metadata: Option<M>,
+        overwrite: Option<bool>,
     ) -> Result<DocumentResponse> {
         #[derive(Serialize)]
-        struct Request {
+        struct Request<M> {
             collection_name: String,
             path: String,
             content: DocumentContent,
             #[serde(skip_serializing_if = "Option::is_none")]
-            metadata: Option<Metadata>,
+            metadata: Option<M>,
             #[serde(skip_serializing_if = "Option::is_none")]
             overwrite: Option<bool>,
         }
 
         let body = Request {
-            collection_name: collection_name.into(),
-            path: path.into(),
+            collection_name,
+            path,
             content,
             metadata,
             overwrite,
         };
 
-        self.client.post("/documents/add-document", &body).await
+        self.client.post_write("/documents/add-document", &body, &self.write_options()).await
+    }
+
+    /// Add many documents to a collection
+    ///
+    /// The API has no batch endpoint, so documents are added individually,
+    /// [`DEFAULT_BATCH_CONCURRENCY`] at a time. A failed document does not
+    /// stop the others; results come back in input order.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, DocumentSpec};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let documents = vec![
+    ///     DocumentSpec::text("a.txt", "First document"),
+    ///     DocumentSpec::text("b.txt", "Second document").overwrite(true),
+    /// ];
+    /// for added in client.documents().add_batch("my_collection", documents).await {
+    ///     if let Err(e) = added.result {
+    ///         eprintln!("{}: {}", added.path, e);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_batch(
+        &self,
+        collection_name: impl Into<String>,
+        documents: Vec<DocumentSpec>,
+    ) -> Vec<BatchAddResult> {
+        self.add_batch_with_concurrency(collection_name, documents, DEFAULT_BATCH_CONCURRENCY)
+            .await
+    }
+
+    /// Add many documents, at most `concurrency` at a time
+    ///
+    /// See [`add_batch`](Self::add_batch). An idempotency key set through
+    /// [`with_options`](Self::with_options) is suffixed with each
+    /// document's position so every write keeps a distinct key.
+    pub async fn add_batch_with_concurrency(
+        &self,
+        collection_name: impl Into<String>,
+        documents: Vec<DocumentSpec>,
+        concurrency: usize,
+    ) -> Vec<BatchAddResult> {
+        use futures_util::stream::{self, StreamExt};
+
+        let collection_name = collection_name.into();
+        stream::iter(documents.into_iter().enumerate())
+            .map(|(i, document)| {
+                let documents = self.with_key_suffix(i);
+                let collection_name = collection_name.clone();
+                async move {
+                    let result = documents
+                        .add_typed(
+                            collection_name,
+                            document.path.clone(),
+                            document.content,
+                            document.metadata,
+                            document.overwrite,
+                        )
+                        .await;
+                    BatchAddResult {
+                        path: document.path,
+                        result,
+                    }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
     }
 
     /// Add a text document
@@ -70,6 +226,20 @@ impl<'a> Documents<'a> {
         self.add(collection_name, path, content, metadata, None).await
     }
 
+    /// Add a document whose text is already split into pages
+    ///
+    /// Page numbers in query results follow the order of `pages`.
+    pub async fn add_text_pages(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        pages: Vec<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        let content = DocumentContent::TextPages { pages };
+        self.add(collection_name, path, content, metadata, None).await
+    }
+
     /// Add a PDF document from base64 data
     ///
     /// Convenience method for adding PDF documents with OCR
@@ -86,9 +256,88 @@ impl<'a> Documents<'a> {
         self.add(collection_name, path, content, metadata, None).await
     }
 
+    /// Add a text document, replacing any document already at `path`
+    ///
+    /// Sends the add with `overwrite` set, so the content and metadata
+    /// replace the existing ones and the document is indexed again.
+    pub async fn upsert_text(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        text: impl Into<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        let content = DocumentContent::Text {
+            text: text.into(),
+        };
+        self.add(collection_name, path, content, metadata, Some(true)).await
+    }
+
+    /// Add a PDF document from base64 data, replacing any document already
+    /// at `path`
+    ///
+    /// See [`upsert_text`](Self::upsert_text).
+    pub async fn upsert_pdf(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        base64_data: impl Into<String>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        let content = DocumentContent::Auto {
+            base64_data: base64_data.into(),
+        };
+        self.add(collection_name, path, content, metadata, Some(true)).await
+    }
+
+    /// Add a document, or update the metadata of the document already at
+    /// `path`
+    ///
+    /// Unlike an upsert, an existing document keeps its content and is not
+    /// indexed again; only `metadata` is written to it. Suits ingestion
+    /// loops that re-run over the same sources. An idempotency key set
+    /// through [`with_options`](Self::with_options) is suffixed with
+    /// `-update` for the fallback update.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, DocumentContent, Metadata};
+    /// # async fn example(metadata: Metadata) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let content = DocumentContent::Text { text: "Document text".to_string() };
+    /// client
+    ///     .documents()
+    ///     .add_or_update("my_collection", "document.txt", content, Some(metadata))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_or_update(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        content: DocumentContent,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        let collection_name = collection_name.into();
+        let path = path.into();
+        match self
+            .add_typed(collection_name.clone(), path.clone(), content, metadata.clone(), None)
+            .await
+        {
+            Err(Error::Conflict(_)) => {
+                self.with_key_suffix("update")
+                    .update(collection_name, path, metadata, None)
+                    .await
+            }
+            result => result,
+        }
+    }
+
     /// Add a PDF document from file path
     ///
     /// Reads the file and encodes it as base64
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn add_pdf_file(
         &self,
         collection_name: impl Into<String>,
@@ -104,6 +353,100 @@ impl<'a> Documents<'a> {
         self.add_pdf(collection_name, document_path, base64_data, metadata).await
     }
 
+    /// Add a document from a file of any supported type
+    ///
+    /// The file type is detected from its content: recognized binary
+    /// formats (PDF, images, office documents, ...) are sent for parsing,
+    /// while `.txt` and `.md` files and other unrecognized files are sent
+    /// as text when they are valid UTF-8. Binary files are base64 encoded
+    /// as they are read, without holding the raw bytes in memory alongside
+    /// the encoding.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// for name in ["notes.md", "scan.png", "report.docx"] {
+    ///     client
+    ///         .documents()
+    ///         .add_file("my_collection", name, format!("./docs/{}", name), None)
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn add_file(
+        &self,
+        collection_name: impl Into<String>,
+        document_path: impl Into<String>,
+        file_path: impl AsRef<std::path::Path>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        let content = file_content(file_path.as_ref()).await?;
+        self.add(collection_name, document_path, content, metadata, None).await
+    }
+
+    /// Add a document from a file on the web
+    ///
+    /// Downloads `url`, without sending the API key, and adds its content
+    /// like [`add_file`](Self::add_file). The type is taken from the
+    /// response's `Content-Type`: text is sent as text, and PDFs, images and
+    /// office documents for parsing. HTML and other types fail with
+    /// [`Error::UnsupportedContentType`], as an HTML response is usually a
+    /// landing page rather than the document itself. Downloads larger than
+    /// [`DEFAULT_MAX_DOWNLOAD_SIZE`] fail with [`Error::ResourceTooLarge`];
+    /// use [`add_url_with_limit`](Self::add_url_with_limit) to change it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// client
+    ///     .documents()
+    ///     .add_url(
+    ///         "papers",
+    ///         "arxiv_1706.03762.pdf",
+    ///         "https://arxiv.org/pdf/1706.03762.pdf",
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn add_url(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        url: impl AsRef<str>,
+        metadata: Option<Metadata>,
+    ) -> Result<DocumentResponse> {
+        self.add_url_with_limit(collection_name, path, url, metadata, DEFAULT_MAX_DOWNLOAD_SIZE)
+            .await
+    }
+
+    /// Add a document from a file on the web, downloading at most
+    /// `max_bytes`
+    ///
+    /// See [`add_url`](Self::add_url).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn add_url_with_limit(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        url: impl AsRef<str>,
+        metadata: Option<Metadata>,
+        max_bytes: u64,
+    ) -> Result<DocumentResponse> {
+        let url = url.as_ref();
+        let (content_type, bytes) = self.client.fetch(url, max_bytes, &self.options).await?;
+        let content = url_content(url, content_type.as_deref(), bytes)?;
+        self.add(collection_name, path, content, metadata, None).await
+    }
+
     /// Update a document's metadata or index status
     pub async fn update(
         &self,
@@ -111,25 +454,134 @@ impl<'a> Documents<'a> {
         path: impl Into<String>,
         metadata: Option<Metadata>,
         index_status: Option<IndexStatus>,
+    ) -> Result<DocumentResponse> {
+        self.update_typed(collection_name.into(), path.into(), metadata, index_status)
+            .await
+    }
+
+    pub(crate) async fn update_typed<M: Serialize>(
+        &self,
+        collection_name: String,
+        path: String,
+        metadata: Option<M>,
+        index_status: Option<IndexStatus>,
     ) -> Result<DocumentResponse> {
         #[derive(Serialize)]
-        struct Request {
+        struct Request<M> {
             collection_name: String,
             path: String,
             #[serde(skip_serializing_if = "Option::is_none")]
-            metadata: Option<Metadata>,
+            metadata: Option<M>,
             #[serde(skip_serializing_if = "Option::is_none")]
             index_status: Option<IndexStatus>,
         }
 
         let body = Request {
-            collection_name: collection_name.into(),
-            path: path.into(),
-            metadata,
+            collection_name,
+            path,
+            metadata: self.client.with_default_metadata(metadata)?,
             index_status,
         };
 
-        self.client.post("/documents/update-document", &body).await
+        self.client.post_write("/documents/update-document", &body, &self.write_options()).await
+    }
+
+    /// Change a document's metadata in place
+    ///
+    /// [`update`](Self::update) replaces the metadata wholesale; this reads
+    /// the current metadata, lets `patch` modify it, and writes the result
+    /// back. A document without metadata starts from an empty map. Returns
+    /// the metadata written.
+    ///
+    /// A write made by someone else between the read and the write back is
+    /// lost; [`patch_metadata_checked`](Self::patch_metadata_checked)
+    /// narrows that window.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, MetadataValue};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// client
+    ///     .documents()
+    ///     .patch_metadata("my_collection", "doc1.txt", |metadata| {
+    ///         metadata.insert("reviewed".to_string(), MetadataValue::String("yes".to_string()));
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn patch_metadata(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        patch: impl FnOnce(&mut Metadata),
+    ) -> Result<Metadata> {
+        self.patch_metadata_inner(collection_name.into(), path.into(), patch, false)
+            .await
+    }
+
+    /// Change a document's metadata in place, failing if it changed while
+    /// being patched
+    ///
+    /// Like [`patch_metadata`](Self::patch_metadata), but reads the metadata
+    /// again just before writing and fails with
+    /// [`Error::ConcurrentModification`] without writing if it no longer
+    /// matches what `patch` was given.
+    ///
+    /// This is a best-effort check made by the client, not a precondition
+    /// enforced by the API, which has no conditional update: a write landing
+    /// between the second read and the write back still goes unnoticed.
+    ///
+    /// [`Error::ConcurrentModification`]: crate::Error::ConcurrentModification
+    pub async fn patch_metadata_checked(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        patch: impl FnOnce(&mut Metadata),
+    ) -> Result<Metadata> {
+        self.patch_metadata_inner(collection_name.into(), path.into(), patch, true)
+            .await
+    }
+
+    async fn patch_metadata_inner(
+        &self,
+        collection_name: String,
+        path: String,
+        patch: impl FnOnce(&mut Metadata),
+        check: bool,
+    ) -> Result<Metadata> {
+        // Always read the live metadata, never a cached response
+        let mut read = Documents {
+            client: self.client,
+            options: self.options.clone(),
+        };
+        read.options.refresh_cache = true;
+
+        let original = read
+            .get_info_typed::<Metadata>(collection_name.clone(), path.clone(), None)
+            .await?
+            .document
+            .metadata
+            .unwrap_or_default();
+        let mut metadata = original.clone();
+        patch(&mut metadata);
+
+        if check {
+            let current = read
+                .get_info_typed::<Metadata>(collection_name.clone(), path.clone(), None)
+                .await?
+                .document
+                .metadata
+                .unwrap_or_default();
+            if current != original {
+                return Err(Error::ConcurrentModification { path });
+            }
+        }
+
+        self.update_typed(collection_name, path, Some(&metadata), None)
+            .await?;
+        Ok(metadata)
     }
 
     /// Delete a document
@@ -149,7 +601,183 @@ impl<'a> Documents<'a> {
             path: path.into(),
         };
 
-        self.client.post("/documents/delete-document", &body).await
+        self.client.post_write("/documents/delete-document", &body, &self.write_options()).await
+    }
+
+    /// Delete every document of a collection, keeping the collection
+    ///
+    /// Pages through the collection and deletes its documents
+    /// [`DEFAULT_BATCH_CONCURRENCY`] at a time. A failed delete is reported
+    /// in [`DeleteAllReport::failed`] without stopping the others.
+    ///
+    /// Fails only when listing the collection fails.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let report = client.documents().delete_all("my_collection").await?;
+    /// println!("deleted {}, {} failed", report.deleted, report.failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_all(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Result<DeleteAllReport> {
+        self.delete_all_with_concurrency(collection_name, DEFAULT_BATCH_CONCURRENCY)
+            .await
+    }
+
+    /// Delete every document of a collection, at most `concurrency` at a
+    /// time
+    ///
+    /// See [`delete_all`](Self::delete_all). An idempotency key set through
+    /// [`with_options`](Self::with_options) is suffixed with a running
+    /// count so every delete keeps a distinct key.
+    pub async fn delete_all_with_concurrency(
+        &self,
+        collection_name: impl Into<String>,
+        concurrency: usize,
+    ) -> Result<DeleteAllReport> {
+        use futures_util::stream::{self, StreamExt};
+
+        let collection_name = collection_name.into();
+        let mut report = DeleteAllReport::default();
+        // Failed deletes stay in the collection, so the pages move past them
+        let pages = self.list_pages::<IgnoredAny>(collection_name.clone(), None);
+        let mut pages = std::pin::pin!(pages);
+        let mut sent = 0;
+        while let Some(page) = pages.try_next().await? {
+            let outcomes: Vec<(String, Result<DocumentResponse>)> =
+                stream::iter(page.into_iter().enumerate())
+                    .map(|(i, document)| {
+                        let documents = self.with_key_suffix(sent + i);
+                        let collection_name = collection_name.clone();
+                        async move {
+                            let result = documents
+                                .delete(collection_name, document.path.clone())
+                                .await;
+                            (document.path, result)
+                        }
+                    })
+                    .buffered(concurrency.max(1))
+                    .collect()
+                    .await;
+            sent += outcomes.len();
+            for (path, outcome) in outcomes {
+                match outcome {
+                    Ok(_) => report.deleted += 1,
+                    Err(e) if e.is_not_found() => report.deleted += 1,
+                    Err(e) => report.failed.push((path, e)),
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Submit every document that failed to parse or index again
+    ///
+    /// Each failed document is re-added with its metadata and the content
+    /// it was uploaded with: the stored text when the API returns it,
+    /// otherwise the original file. Documents are resubmitted
+    /// [`DEFAULT_BATCH_CONCURRENCY`] at a time; one whose content cannot be
+    /// retrieved or whose re-add fails is reported in
+    /// [`ReindexReport::still_failing`] without stopping the others. An
+    /// idempotency key set through [`with_options`](Self::with_options) is
+    /// suffixed as in [`delete_all_with_concurrency`](Self::delete_all_with_concurrency).
+    ///
+    /// Fails only when listing the collection fails. Use
+    /// [`Collections::wait_until_indexed`](crate::Collections::wait_until_indexed)
+    /// to find out whether the resubmitted documents index this time.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let report = client.documents().reindex_failed("my_collection").await?;
+    /// for (path, e) in &report.still_failing {
+    ///     eprintln!("{}: {}", path, e);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reindex_failed(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Result<ReindexReport> {
+        use futures_util::stream::StreamExt;
+
+        let collection_name = collection_name.into();
+        let mut report = ReindexReport::default();
+        let mut pages = std::pin::pin!(self.list_pages::<Metadata>(collection_name.clone(), None));
+        let mut sent = 0;
+        while let Some(page) = pages.try_next().await? {
+            let failed: Vec<_> = page
+                .into_iter()
+                .filter(|document| document.index_status.is_failed())
+                .collect();
+            let outcomes: Vec<(String, Result<DocumentResponse>)> =
+                stream::iter(failed.into_iter().enumerate())
+                    .map(|(i, document)| {
+                        let documents = self.with_key_suffix(sent + i);
+                        let collection_name = collection_name.clone();
+                        async move {
+                            let result = match documents
+                                .original_content(&collection_name, &document.path)
+                                .await
+                            {
+                                Ok(content) => {
+                                    documents
+                                        .add_typed(
+                                            collection_name,
+                                            document.path.clone(),
+                                            content,
+                                            document.metadata,
+                                            Some(true),
+                                        )
+                                        .await
+                                }
+                                Err(e) => Err(e),
+                            };
+                            (document.path, result)
+                        }
+                    })
+                    .buffered(DEFAULT_BATCH_CONCURRENCY)
+                    .collect()
+                    .await;
+            sent += outcomes.len();
+            for (path, outcome) in outcomes {
+                match outcome {
+                    Ok(_) => report.resubmitted.push(path),
+                    Err(e) => report.still_failing.push((path, e)),
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Content to re-add a document with: its stored content when the API
+    /// returns it, otherwise its original file
+    pub(crate) async fn original_content(
+        &self,
+        collection_name: &str,
+        path: &str,
+    ) -> Result<DocumentContent> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let info = self
+            .get_info_typed::<IgnoredAny>(collection_name.to_string(), path.to_string(), Some(true))
+            .await?;
+        Ok(match info.document.content {
+            Some(content) => content,
+            None => DocumentContent::Auto {
+                base64_data: general_purpose::STANDARD
+                    .encode(self.get_raw(collection_name, path).await?),
+            },
+        })
     }
 
     /// Get document information
@@ -159,6 +787,16 @@ impl<'a> Documents<'a> {
         path: impl Into<String>,
         include_content: Option<bool>,
     ) -> Result<DocumentInfoResponse> {
+        self.get_info_typed(collection_name.into(), path.into(), include_content)
+            .await
+    }
+
+    pub(crate) async fn get_info_typed<M: DeserializeOwned>(
+        &self,
+        collection_name: String,
+        path: String,
+        include_content: Option<bool>,
+    ) -> Result<DocumentInfoResponse<M>> {
         #[derive(Serialize)]
         struct Request {
             collection_name: String,
@@ -167,13 +805,327 @@ impl<'a> Documents<'a> {
             include_content: Option<bool>,
         }
 
+        let body = Request {
+            collection_name,
+            path,
+            include_content,
+        };
+
+        self.client.post("/documents/get-document-info", &body, &self.options).await
+    }
+
+    /// Check whether a document exists
+    ///
+    /// A missing document gives `Ok(false)`; other failures are returned as
+    /// errors.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// if !client.documents().exists("my_collection", "doc1.txt").await? {
+    ///     client
+    ///         .documents()
+    ///         .add_text("my_collection", "doc1.txt", "Hello", None)
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn exists(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<bool> {
+        match self
+            .get_info_typed::<IgnoredAny>(collection_name.into(), path.into(), None)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wait until a document is indexed and can be queried
+    ///
+    /// Polls the document's index status every `poll_interval`. Fails with
+    /// [`Error::IndexingFailed`] if parsing or indexing fails, and with
+    /// [`Error::WaitTimeout`] if the document is not indexed within
+    /// `timeout`. Returns the document's info once indexed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// client
+    ///     .documents()
+    ///     .add_text("my_collection", "doc1.txt", "Hello", None)
+    ///     .await?;
+    /// client
+    ///     .documents()
+    ///     .wait_until_indexed(
+    ///         "my_collection",
+    ///         "doc1.txt",
+    ///         Duration::from_secs(60),
+    ///         Duration::from_secs(1),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_indexed(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<DocumentInfo> {
+        let collection_name = collection_name.into();
+        let path = path.into();
+        // Polling must see the live status, never a cached response
+        let mut poll = Documents {
+            client: self.client,
+            options: self.options.clone(),
+        };
+        poll.options.refresh_cache = true;
+
+        let started = Instant::now();
+        loop {
+            let document = poll
+                .get_info_typed(collection_name.clone(), path.clone(), None)
+                .await?
+                .document;
+            match document.index_status {
+                IndexStatus::Indexed => return Ok(document),
+                status @ (IndexStatus::ParsingFailed | IndexStatus::IndexingFailed) => {
+                    return Err(Error::IndexingFailed {
+                        path,
+                        status,
+                        reason: document.failure_reason,
+                    })
+                }
+                _ => {}
+            }
+            if started.elapsed() + poll_interval > timeout {
+                return Err(Error::WaitTimeout(timeout));
+            }
+            runtime::sleep(poll_interval).await;
+        }
+    }
+
+    /// Get a document's text as a single string
+    ///
+    /// Text documents are returned as is and pre-paginated ones with their
+    /// pages separated by blank lines. For parsed files such as PDFs, the
+    /// parsed text of each page is fetched, starting from page 1 until the
+    /// document has no more pages.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let text = client.documents().get_text("my_collection", "report.pdf").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_text(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<String> {
+        let collection_name = collection_name.into();
+        let path = path.into();
+        let info = self
+            .get_info_typed::<IgnoredAny>(collection_name.clone(), path.clone(), Some(true))
+            .await?;
+        match info.document.content {
+            Some(DocumentContent::Text { text }) => return Ok(text),
+            Some(DocumentContent::TextPages { pages }) => return Ok(pages.join("\n\n")),
+            Some(DocumentContent::Auto { .. }) | None => {}
+        }
+
+        let pages: Vec<String> = self
+            .pages_stream(collection_name, path)
+            .map_ok(|page| page.content.unwrap_or_default())
+            .try_collect()
+            .await?;
+        Ok(pages.join("\n\n"))
+    }
+
+    /// Get a new signed URL for a document's original file
+    ///
+    /// Signed URLs expire; call this when the
+    /// [`file_url`](crate::DocumentInfo::file_url) of an earlier response has
+    /// passed its `file_url_expires_at`. Returns `None` for a document with
+    /// no stored file, e.g. one added as [`DocumentContent::Text`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// if let Some(url) = client
+    ///     .documents()
+    ///     .refresh_file_url("my_collection", "report.pdf")
+    ///     .await?
+    /// {
+    ///     println!("open {}", url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn refresh_file_url(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<Option<String>> {
+        #[derive(Serialize)]
+        struct Request {
+            collection_name: String,
+            path: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            document: FileUrl,
+        }
+
+        #[derive(Deserialize)]
+        struct FileUrl {
+            #[serde(default)]
+            file_url: Option<String>,
+        }
+
         let body = Request {
             collection_name: collection_name.into(),
             path: path.into(),
-            include_content,
         };
+        // A cached response may hold an expired URL
+        let mut options = self.options.clone();
+        options.refresh_cache = true;
+        let response: Response = self
+            .client
+            .post("/documents/get-document-info", &body, &options)
+            .await?;
+        Ok(response.document.file_url)
+    }
+
+    /// Download the original file uploaded for a document
+    ///
+    /// Fetches a signed URL for the file, then the file itself. Fails with
+    /// [`Error::NoStoredFile`] for a document with no stored file.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let bytes = client.documents().get_raw("my_collection", "report.pdf").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_raw(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Result<Vec<u8>> {
+        let path = path.into();
+        match self.refresh_file_url(collection_name, path.clone()).await? {
+            Some(file_url) => self.client.download(&file_url, &self.options).await,
+            None => Err(Error::NoStoredFile { path }),
+        }
+    }
 
-        self.client.post("/documents/get-document-info", &body).await
+    /// Download the original file uploaded for a document to `file_path`
+    ///
+    /// Returns the number of bytes written.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn get_raw_to_file(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        file_path: impl AsRef<std::path::Path>,
+    ) -> Result<u64> {
+        let bytes = self.get_raw(collection_name, path).await?;
+        tokio::fs::write(file_path, &bytes).await?;
+        Ok(bytes.len() as u64)
+    }
+
+    /// Stream a document's content into a writer
+    ///
+    /// Unlike [`get_info`](Self::get_info) with `include_content`, the
+    /// content is written out as the response arrives instead of being
+    /// buffered in memory, which suits very large documents.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let mut file = tokio::fs::File::create("report.txt").await?;
+    /// client
+    ///     .documents()
+    ///     .get_content_to_writer("my_collection", "report.pdf", &mut file)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn get_content_to_writer<W>(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        writer: &mut W,
+    ) -> Result<u64>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use crate::json_stream::StringFieldExtractor;
+        use futures_util::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        #[derive(Serialize)]
+        struct Request {
+            collection_name: String,
+            path: String,
+            include_content: bool,
+        }
+
+        let body = Request {
+            collection_name: collection_name.into(),
+            path: path.into(),
+            include_content: true,
+        };
+
+        let mut stream = self
+            .client
+            .post_stream("/documents/get-document-info", &body, &self.options)
+            .await?;
+
+        // The content sits at `{"document": {"content": "..."}}`
+        let mut extractor = StringFieldExtractor::new("content", 2);
+        let mut buffer = Vec::new();
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            extractor.feed(&chunk?, &mut buffer)?;
+            writer.write_all(&buffer).await?;
+            written += buffer.len() as u64;
+            buffer.clear();
+            if extractor.is_done() {
+                break;
+            }
+        }
+        writer.flush().await?;
+
+        extractor.finish()?;
+        Ok(written)
     }
 
     /// Get list of documents in a collection
@@ -183,6 +1135,16 @@ impl<'a> Documents<'a> {
         limit: Option<u32>,
         path_gt: Option<String>,
     ) -> Result<DocumentInfoListResponse> {
+        self.get_info_list_typed(collection_name.into(), limit, path_gt)
+            .await
+    }
+
+    pub(crate) async fn get_info_list_typed<M: DeserializeOwned>(
+        &self,
+        collection_name: String,
+        limit: Option<u32>,
+        path_gt: Option<String>,
+    ) -> Result<DocumentInfoListResponse<M>> {
         #[derive(Serialize)]
         struct Request {
             collection_name: String,
@@ -193,12 +1155,135 @@ impl<'a> Documents<'a> {
         }
 
         let body = Request {
-            collection_name: collection_name.into(),
+            collection_name,
             limit,
             path_gt,
         };
 
-        self.client.post("/documents/get-document-info-list", &body).await
+        self.client.post("/documents/get-document-info-list", &body, &self.options).await
+    }
+
+    /// Page through the documents of a collection in path order, starting
+    /// after `path_gt`
+    ///
+    /// Each page of up to [`LIST_PAGE_SIZE`] documents is requested only
+    /// when the stream is polled for it. The stream ends after the last
+    /// page, or after the first error.
+    pub(crate) fn list_pages<M: DeserializeOwned + 'a>(
+        &self,
+        collection_name: String,
+        path_gt: Option<String>,
+    ) -> impl Stream<Item = Result<Vec<DocumentInfo<M>>>> + 'a {
+        let client = self.client;
+        let options = self.options.clone();
+        // `None` once the last page has been returned
+        stream::try_unfold(Some(path_gt), move |cursor| {
+            let documents = Documents {
+                client,
+                options: options.clone(),
+            };
+            let collection_name = collection_name.clone();
+            async move {
+                let Some(path_gt) = cursor else {
+                    return Ok(None);
+                };
+                let page = documents
+                    .get_info_list_typed::<M>(collection_name, Some(LIST_PAGE_SIZE), path_gt)
+                    .await?;
+                let next = match page.documents.last() {
+                    Some(last) if page.documents.len() >= LIST_PAGE_SIZE as usize => {
+                        Some(Some(last.path.clone()))
+                    }
+                    _ => None,
+                };
+                Ok(Some((page.documents, next)))
+            }
+        })
+    }
+
+    /// Count the documents of a collection by index status
+    ///
+    /// Pages through the whole document list, so this takes one request
+    /// per 1024 documents.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example(client: Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let report = client.documents().index_report("my_collection").await?;
+    /// println!(
+    ///     "{}/{} indexed, {} failed",
+    ///     report.indexed,
+    ///     report.total(),
+    ///     report.failed(),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn index_report(
+        &self,
+        collection_name: impl Into<String>,
+    ) -> Result<CollectionIndexReport> {
+        let collection_name = collection_name.into();
+        let mut report = CollectionIndexReport {
+            collection_name: collection_name.clone(),
+            ..Default::default()
+        };
+        let mut pages = std::pin::pin!(self.list_pages::<IgnoredAny>(collection_name, None));
+        while let Some(page) = pages.try_next().await? {
+            for document in &page {
+                report.add(document.index_status);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Count the documents of a collection
+    ///
+    /// A single request to the status endpoint, however large the
+    /// collection. The whole collection is counted: unlike queries, the
+    /// count is not restricted to the client's
+    /// [default metadata](crate::ClientBuilder::default_metadata).
+    ///
+    /// The API cannot count by metadata, so passing a `filter` fails with
+    /// [`Error::InvalidParameter`] without sending anything. Count matches
+    /// of a filter with a query instead.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let total = client.documents().count("my_collection", None).await?;
+    /// println!("{} documents", total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn count(
+        &self,
+        collection_name: impl Into<String>,
+        filter: Option<Filter>,
+    ) -> Result<u64> {
+        #[derive(Serialize)]
+        struct Request {
+            collection_name: String,
+        }
+        #[derive(Deserialize)]
+        struct Response {
+            num_documents: u64,
+        }
+
+        if filter.is_some() {
+            return Err(Error::InvalidParameter(
+                "documents cannot be counted by metadata filter".to_string(),
+            ));
+        }
+        let body = Request {
+            collection_name: collection_name.into(),
+        };
+        let response: Response =
+            self.client.post("/status/get-status", &body, &self.options).await?;
+        Ok(response.num_documents)
     }
 
     /// Get information about a specific page
@@ -225,6 +1310,246 @@ impl<'a> Documents<'a> {
             include_content,
         };
 
-        self.client.post("/documents/get-page-info", &body).await
+        self.client.post("/documents/get-page-info", &body, &self.options).await
+    }
+
+    /// Stream the pages of a document, with their content
+    ///
+    /// Each page is fetched only when the stream is polled for it, starting
+    /// from page 1, so very large documents can be processed a page at a
+    /// time. The stream ends after the last page, or after the first error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let pages = client.documents().pages_stream("my_collection", "report.pdf");
+    /// let mut pages = std::pin::pin!(pages);
+    /// while let Some(page) = pages.next().await {
+    ///     let page = page?;
+    ///     println!("page {}: {:?}", page.page_number, page.content);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pages_stream(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> impl Stream<Item = Result<PageInfo>> + 'a {
+        let documents = Documents {
+            client: self.client,
+            options: self.options.clone(),
+        };
+        let collection_name = collection_name.into();
+        let path = path.into();
+        stream::try_unfold(1, move |page_number| {
+            let documents = Documents {
+                client: documents.client,
+                options: documents.options.clone(),
+            };
+            let collection_name = collection_name.clone();
+            let path = path.clone();
+            async move {
+                match documents
+                    .get_page_info(collection_name, path, page_number, Some(true))
+                    .await
+                {
+                    Ok(response) => Ok(Some((response.page, page_number + 1))),
+                    // Running past the last page
+                    Err(e) if e.is_not_found() && page_number > 1 => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        })
+    }
+
+    /// Get a range of pages of a document, with their content
+    ///
+    /// The first page of the range is fetched on its own, the rest
+    /// [`DEFAULT_BATCH_CONCURRENCY`] at a time, and pages are returned in
+    /// page order. Pages past the end of the document are left out, so a
+    /// window around a matched page can be asked for without knowing the
+    /// page count: no more pages are requested after the first one that
+    /// does not exist. When even the first page does not exist, the
+    /// document is looked up so a missing document still fails with
+    /// [`Error::NotFound`].
+    ///
+    /// Ranges longer than [`MAX_PAGE_RANGE`] pages fail with
+    /// [`Error::InvalidParameter`] before anything is sent.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// // Two pages either side of a match on page 7
+    /// let window = client
+    ///     .documents()
+    ///     .get_pages("my_collection", "report.pdf", 5..=9)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_pages(
+        &self,
+        collection_name: impl Into<String>,
+        path: impl Into<String>,
+        pages: RangeInclusive<u32>,
+    ) -> Result<Vec<PageInfo>> {
+        use futures_util::StreamExt;
+
+        if pages.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (first, last) = pages.into_inner();
+        if u64::from(last - first) >= u64::from(MAX_PAGE_RANGE) {
+            return Err(Error::InvalidParameter(format!(
+                "page range {}..={} is longer than {} pages",
+                first, last, MAX_PAGE_RANGE
+            )));
+        }
+
+        let collection_name = collection_name.into();
+        let path = path.into();
+        // `None` for a page past the end of the document
+        let fetch = |page_number| {
+            let response =
+                self.get_page_info(collection_name.clone(), path.clone(), page_number, Some(true));
+            async move {
+                match response.await {
+                    Ok(response) => Ok(Some(response.page)),
+                    Err(e) if e.is_not_found() => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        };
+
+        let Some(first_page) = fetch(first).await? else {
+            // Tell a window past the end from a missing document
+            self.get_info(collection_name.clone(), path.clone(), None).await?;
+            return Ok(Vec::new());
+        };
+        let mut window = vec![first_page];
+        if first < last {
+            let rest = stream::iter(first + 1..=last)
+                .map(fetch)
+                .buffered(DEFAULT_BATCH_CONCURRENCY);
+            let mut rest = std::pin::pin!(rest);
+            while let Some(Some(page)) = rest.try_next().await? {
+                window.push(page);
+            }
+        }
+        Ok(window)
+    }
+}
+
+/// Bytes read at a time while base64 encoding a file; a multiple of 3 so
+/// the chunks encode without padding
+#[cfg(not(target_arch = "wasm32"))]
+const ENCODE_CHUNK_SIZE: usize = 3 * 64 * 1024;
+
+/// Content for a file, as text or as base64 data to parse depending on its
+/// detected type
+#[cfg(not(target_arch = "wasm32"))]
+async fn file_content(file_path: &std::path::Path) -> Result<DocumentContent> {
+    use base64::{engine::general_purpose, Engine as _};
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(file_path).await?;
+    let mut chunk = vec![0; ENCODE_CHUNK_SIZE];
+    let mut filled = fill(&mut file, &mut chunk).await?;
+
+    let is_text_extension = file_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            ["txt", "md"].iter().any(|text| extension.eq_ignore_ascii_case(text))
+        });
+    if is_text_extension || infer::get(&chunk[..filled]).is_none() {
+        let mut bytes = chunk[..filled].to_vec();
+        file.read_to_end(&mut bytes).await?;
+        return Ok(text_content(bytes));
+    }
+
+    let mut base64_data = String::new();
+    while filled > 0 {
+        general_purpose::STANDARD.encode_string(&chunk[..filled], &mut base64_data);
+        filled = fill(&mut file, &mut chunk).await?;
+    }
+    Ok(DocumentContent::Auto { base64_data })
+}
+
+/// Content for a resource fetched from `url`, picked from its content type
+/// or, when the server gives none, from the bytes themselves
+#[cfg(not(target_arch = "wasm32"))]
+fn url_content(url: &str, content_type: Option<&str>, bytes: Vec<u8>) -> Result<DocumentContent> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let mime = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let binary = || DocumentContent::Auto {
+        base64_data: general_purpose::STANDARD.encode(&bytes),
+    };
+    Ok(match mime.as_str() {
+        "" | "application/octet-stream" if infer::get(&bytes).is_some() => binary(),
+        "" | "application/octet-stream" => text_content(bytes),
+        // Usually a landing or login page served instead of the document
+        "text/html" | "application/xhtml+xml" => {
+            return Err(Error::UnsupportedContentType {
+                url: url.split('?').next().unwrap_or(url).to_string(),
+                content_type: mime,
+            })
+        }
+        mime if mime.starts_with("text/") => text_content(bytes),
+        "application/pdf" | "application/msword" | "application/rtf" => binary(),
+        mime if mime.starts_with("image/")
+            || mime.starts_with("application/vnd.openxmlformats-officedocument.")
+            || mime.starts_with("application/vnd.ms-")
+            || mime.starts_with("application/vnd.oasis.opendocument.") =>
+        {
+            binary()
+        }
+        _ => {
+            return Err(Error::UnsupportedContentType {
+                url: url.split('?').next().unwrap_or(url).to_string(),
+                content_type: mime,
+            })
+        }
+    })
+}
+
+/// Text content when `bytes` are valid UTF-8, otherwise base64 data to
+/// parse
+#[cfg(not(target_arch = "wasm32"))]
+fn text_content(bytes: Vec<u8>) -> DocumentContent {
+    use base64::{engine::general_purpose, Engine as _};
+
+    match String::from_utf8(bytes) {
+        Ok(text) => DocumentContent::Text { text },
+        Err(e) => DocumentContent::Auto {
+            base64_data: general_purpose::STANDARD.encode(e.into_bytes()),
+        },
+    }
+}
+
+/// Read into `buffer` until it is full or the file ends, returning the
+/// bytes read
+#[cfg(not(target_arch = "wasm32"))]
+async fn fill(file: &mut tokio::fs::File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    use tokio::io::AsyncReadExt;
+
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..]).await? {
+            0 => break,
+            read => filled += read,
+        }
     }
+    Ok(filled)
 }
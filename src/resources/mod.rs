@@ -1,9 +1,17 @@
+pub mod admin;
 pub mod collections;
 pub mod documents;
 pub mod models;
+pub mod parsers;
 pub mod queries;
+pub mod typed_collection;
 
-pub use collections::Collections;
-pub use documents::Documents;
-pub use models::Models;
-pub use queries::Queries;
+pub use admin::Admin;
+pub use collections::{Collections, INDEX_POLL_INTERVAL};
+pub use documents::{
+    Documents, DEFAULT_BATCH_CONCURRENCY, DEFAULT_MAX_DOWNLOAD_SIZE, MAX_PAGE_RANGE,
+};
+pub use models::{Models, DEFAULT_RERANK_CHUNK_SIZE};
+pub use parsers::Parsers;
+pub use queries::{Queries, Search, MAX_TOP_DOCUMENTS_K, MAX_TOP_PAGES_K, MAX_TOP_SNIPPETS_K};
+pub use typed_collection::TypedCollection;
@@ -1,16 +1,59 @@
-use crate::client::Client;
+use crate::client::{Client, RequestOptions};
 use crate::error::Result;
-use crate::types::{RerankDocument, RerankResponse};
-use serde::Serialize;
+use crate::types::{
+    DocumentResult, ModelListResponse, RerankDocument, RerankParams, RerankResponse,
+    RerankResult, Reranker, TopDocumentsResponse, TopSnippetsResponse,
+};
+use std::time::Duration;
+
+/// Documents sent per rerank request; larger sets are split into chunks
+pub const DEFAULT_RERANK_CHUNK_SIZE: usize = 1024;
+/// Chunks of a large rerank call in flight at once
+const RERANK_CHUNK_CONCURRENCY: usize = 4;
 
 /// Models resource for reranking operations
 pub struct Models<'a> {
     client: &'a Client,
+    options: RequestOptions,
 }
 
 impl<'a> Models<'a> {
     pub(crate) fn new(client: &'a Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// List the models available to the API key
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// for model in client.models().list().await?.models {
+    ///     println!("{}", model.model_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list(&self) -> Result<ModelListResponse> {
+        self.client
+            .post("/models/get-model-list", &serde_json::json!({}), &self.options)
+            .await
     }
 
     /// Rerank documents based on relevance to a query
@@ -18,7 +61,7 @@ impl<'a> Models<'a> {
     /// # Arguments
     /// * `query` - The query to rank documents against
     /// * `documents` - List of documents to rerank
-    /// * `model_id` - Optional model ID (defaults to best available model)
+    /// * `model_id` - Optional reranker model (defaults to best available model)
     /// * `top_k` - Optional number of top results to return
     ///
     /// # Example
@@ -50,26 +93,234 @@ impl<'a> Models<'a> {
         &self,
         query: impl Into<String>,
         documents: Vec<RerankDocument>,
-        model_id: Option<String>,
+        model_id: Option<Reranker>,
         top_k: Option<u32>,
     ) -> Result<RerankResponse> {
-        #[derive(Serialize)]
-        struct Request {
-            query: String,
-            documents: Vec<RerankDocument>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            model_id: Option<String>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            top_k: Option<u32>,
-        }
-
-        let body = Request {
+        let params = RerankParams {
             query: query.into(),
             documents,
             model_id,
             top_k,
+            chunk_size: None,
         };
+        self.rerank_with(params).await
+    }
 
-        self.client.post("/models/rerank", &body).await
+    /// Rerank documents, with optional parameters set through
+    /// [`RerankParams`]
+    ///
+    /// More than [`DEFAULT_RERANK_CHUNK_SIZE`] documents (or the
+    /// [`chunk_size`](RerankParams::chunk_size) set on `params`) are split
+    /// into chunks reranked concurrently. The chunk results are merged into
+    /// one response, best first, with each `index` pointing into the full
+    /// document list.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, RerankParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let params = RerankParams::new(
+    ///     "systems programming",
+    ///     [
+    ///         ("doc1", "Rust is a systems programming language"),
+    ///         ("doc2", "Python is a high-level programming language"),
+    ///     ],
+    /// );
+    /// let response = client.models().rerank_with(params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rerank_with(&self, params: RerankParams) -> Result<RerankResponse> {
+        use futures_util::stream::{self, StreamExt, TryStreamExt};
+
+        let chunk_size = params.chunk_size.unwrap_or(DEFAULT_RERANK_CHUNK_SIZE);
+        if params.documents.len() <= chunk_size {
+            return self.client.post("/models/rerank", &params, &self.options).await;
+        }
+
+        // Each chunk keeps the full top_k, since the best results overall
+        // may all come from one chunk
+        let chunks: Vec<(usize, RerankParams)> = params
+            .documents
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, documents)| {
+                let chunk = RerankParams {
+                    query: params.query.clone(),
+                    documents: documents.to_vec(),
+                    model_id: params.model_id.clone(),
+                    top_k: params.top_k,
+                    chunk_size: None,
+                };
+                (i * chunk_size, chunk)
+            })
+            .collect();
+        let responses: Vec<(usize, RerankResponse)> = stream::iter(chunks)
+            .map(|(offset, chunk)| async move {
+                let response: RerankResponse =
+                    self.client.post("/models/rerank", &chunk, &self.options).await?;
+                Ok::<_, crate::Error>((offset, response))
+            })
+            .buffered(RERANK_CHUNK_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let mut results: Vec<RerankResult> = responses
+            .into_iter()
+            .flat_map(|(offset, response)| {
+                response.results.into_iter().map(move |mut result| {
+                    result.index += offset;
+                    result
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if let Some(top_k) = params.top_k {
+            results.truncate(top_k as usize);
+        }
+        Ok(RerankResponse { results })
+    }
+
+    /// Rerank plain strings based on relevance to a query
+    ///
+    /// Each text is sent with its position in `texts` as ID. Results are
+    /// ordered best first, with [`RerankResult::index`] pointing back into
+    /// `texts`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let candidates = ["Python is a scripting language", "Rust is a systems language"];
+    /// let response = client
+    ///     .models()
+    ///     .rerank_texts("systems programming", &candidates, None, Some(1))
+    ///     .await?;
+    /// println!("best match: {}", candidates[response.results[0].index]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rerank_texts<S: AsRef<str>>(
+        &self,
+        query: impl Into<String>,
+        texts: &[S],
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<RerankResponse> {
+        let documents = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| RerankDocument::new(i.to_string(), text.as_ref()))
+            .collect();
+        let mut response = self.rerank(query, documents, model_id, top_k).await?;
+        for result in &mut response.results {
+            if let Ok(index) = result.id.parse() {
+                result.index = index;
+            }
+        }
+        response
+            .results
+            .sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(response)
+    }
+
+    /// Rerank the snippets of a search response against `query`
+    ///
+    /// Returns the snippets best first, each with its `score` replaced by
+    /// the rerank score. With `top_k`, only the best `top_k` are kept.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let query = "what is rust";
+    /// let candidates = client
+    ///     .queries()
+    ///     .top_snippets("my_collection", query, 50, None, None, None, None)
+    ///     .await?;
+    /// let reranked = client
+    ///     .models()
+    ///     .rerank_snippets(query, candidates, None, Some(10))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rerank_snippets<M>(
+        &self,
+        query: impl Into<String>,
+        response: TopSnippetsResponse<M>,
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<TopSnippetsResponse<M>> {
+        let results = self
+            .rerank_results(
+                query,
+                response.results,
+                |snippet| snippet.content.clone(),
+                |snippet, score| snippet.score = score,
+                model_id,
+                top_k,
+            )
+            .await?;
+        Ok(TopSnippetsResponse { results })
+    }
+
+    /// Rerank the documents of a search response against `query`
+    ///
+    /// `text` supplies the text to rank each document by, e.g. its
+    /// `content` when the query set
+    /// [`include_content`](crate::TopDocumentsParams::include_content), or
+    /// a summary from its metadata. Returns the
+    /// documents best first, each with its `score` replaced by the rerank
+    /// score. With `top_k`, only the best `top_k` are kept.
+    pub async fn rerank_documents<M>(
+        &self,
+        query: impl Into<String>,
+        response: TopDocumentsResponse<M>,
+        text: impl Fn(&DocumentResult<M>) -> String,
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<TopDocumentsResponse<M>> {
+        let results = self
+            .rerank_results(
+                query,
+                response.results,
+                text,
+                |document, score| document.score = score,
+                model_id,
+                top_k,
+            )
+            .await?;
+        Ok(TopDocumentsResponse { results })
+    }
+
+    /// Rerank `items` by the text `text` gives for each, setting each kept
+    /// item's score with `set_score`
+    async fn rerank_results<T>(
+        &self,
+        query: impl Into<String>,
+        items: Vec<T>,
+        text: impl Fn(&T) -> String,
+        set_score: impl Fn(&mut T, f64),
+        model_id: Option<Reranker>,
+        top_k: Option<u32>,
+    ) -> Result<Vec<T>> {
+        if items.is_empty() {
+            return Ok(items);
+        }
+        let texts: Vec<String> = items.iter().map(text).collect();
+        let response = self.rerank_texts(query, &texts, model_id, top_k).await?;
+        let mut items: Vec<Option<T>> = items.into_iter().map(Some).collect();
+        let mut reranked = Vec::with_capacity(response.results.len());
+        for result in response.results {
+            if let Some(mut item) = items.get_mut(result.index).and_then(Option::take) {
+                set_score(&mut item, result.score);
+                reranked.push(item);
+            }
+        }
+        Ok(reranked)
     }
 }
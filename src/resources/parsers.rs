@@ -0,0 +1,82 @@
+use crate::client::{Client, RequestOptions};
+use crate::error::Result;
+use crate::types::ParseDocumentResponse;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Parsers resource for extracting text from documents without indexing them
+pub struct Parsers<'a> {
+    client: &'a Client,
+    options: RequestOptions,
+}
+
+impl<'a> Parsers<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Parse a PDF or image into text, one string per page
+    ///
+    /// Runs the same parsing and OCR as adding the document to a
+    /// collection, but stores nothing.
+    ///
+    /// # Arguments
+    /// * `base64_data` - The document's bytes, base64 encoded
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::Client;
+    /// # async fn example(base64_pdf: String) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let parsed = client.parsers().parse_document(base64_pdf).await?;
+    /// for (i, page) in parsed.pages.iter().enumerate() {
+    ///     println!("page {}: {} chars", i + 1, page.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn parse_document(
+        &self,
+        base64_data: impl Into<String>,
+    ) -> Result<ParseDocumentResponse> {
+        #[derive(Serialize)]
+        struct Request {
+            base64_data: String,
+        }
+
+        let body = Request {
+            base64_data: base64_data.into(),
+        };
+
+        self.client.post("/parsers/parse-document", &body, &self.options).await
+    }
+
+    /// Parse a PDF or image from a file path
+    ///
+    /// Reads the file and encodes it as base64
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn parse_file(
+        &self,
+        file_path: impl AsRef<std::path::Path>,
+    ) -> Result<ParseDocumentResponse> {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let bytes = tokio::fs::read(file_path).await?;
+        self.parse_document(general_purpose::STANDARD.encode(&bytes)).await
+    }
+}
@@ -1,18 +1,64 @@
-use crate::client::Client;
-use crate::error::Result;
+use crate::client::{Client, RequestOptions};
+use crate::error::{Error, Result};
 use crate::types::{
-    Filter, LatencyMode, TopDocumentsResponse, TopPagesResponse, TopSnippetsResponse,
+    Filter, LatencyMode, Reranker, TopDocumentsParams, TopDocumentsResponse, TopPagesParams,
+    TopPagesResponse, TopSnippetsParams, TopSnippetsResponse,
 };
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// Results returned by a [`Search`] that does not set [`Search::k`]
+const DEFAULT_SEARCH_K: u32 = 10;
+/// Most documents a top documents query may ask for, including skipped ones
+pub const MAX_TOP_DOCUMENTS_K: u32 = 2048;
+/// Most pages a top pages query may ask for, including skipped ones
+pub const MAX_TOP_PAGES_K: u32 = 1024;
+/// Most snippets a top snippets query may ask for, including skipped ones
+pub const MAX_TOP_SNIPPETS_K: u32 = 128;
 
 /// Queries resource for searching documents
 pub struct Queries<'a> {
     client: &'a Client,
+    options: RequestOptions,
 }
 
 impl<'a> Queries<'a> {
     pub(crate) fn new(client: &'a Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Start a fluent search of `collection_name`
+    ///
+    /// See [`Search`].
+    pub fn search(self, collection_name: impl Into<String>) -> Search<'a> {
+        Search {
+            queries: self,
+            collection_name: collection_name.into(),
+            query: String::new(),
+            k: DEFAULT_SEARCH_K,
+            filter: None,
+            include_metadata: None,
+            include_content: None,
+            latency_mode: None,
+            reranker: None,
+            precise: None,
+            offset: 0,
+        }
     }
 
     /// Search for top documents matching a query
@@ -24,7 +70,8 @@ impl<'a> Queries<'a> {
     /// * `filter` - Optional metadata filter
     /// * `include_metadata` - Whether to include metadata in results
     /// * `latency_mode` - Latency/quality tradeoff
-    /// * `reranker` - Optional reranker model ID
+    /// * `reranker` - Optional reranker model
+    #[allow(clippy::too_many_arguments)]
     pub async fn top_documents(
         &self,
         collection_name: impl Into<String>,
@@ -33,34 +80,58 @@ impl<'a> Queries<'a> {
         filter: Option<Filter>,
         include_metadata: Option<bool>,
         latency_mode: Option<LatencyMode>,
-        reranker: Option<String>,
+        reranker: Option<Reranker>,
     ) -> Result<TopDocumentsResponse> {
-        #[derive(Serialize)]
-        struct Request {
-            collection_name: String,
-            query: String,
-            k: u32,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            filter: Option<Filter>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            include_metadata: Option<bool>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            latency_mode: Option<LatencyMode>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            reranker: Option<String>,
-        }
-
-        let body = Request {
+        let params = TopDocumentsParams {
             collection_name: collection_name.into(),
             query: query.into(),
             k,
             filter,
             include_metadata,
+            include_content: None,
             latency_mode,
             reranker,
+            offset: 0,
         };
+        self.top_documents_typed(params).await
+    }
 
-        self.client.post("/queries/top-documents", &body).await
+    /// Search for top documents, with optional parameters set through
+    /// [`TopDocumentsParams`]
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, TopDocumentsParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let params = TopDocumentsParams::new("my_collection", "what is rust", 10)
+    ///     .include_metadata(true);
+    /// let results = client.queries().top_documents_with(params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn top_documents_with(
+        &self,
+        params: TopDocumentsParams,
+    ) -> Result<TopDocumentsResponse> {
+        self.top_documents_typed(params).await
+    }
+
+    pub(crate) async fn top_documents_typed<M: DeserializeOwned>(
+        &self,
+        mut params: TopDocumentsParams,
+    ) -> Result<TopDocumentsResponse<M>> {
+        params.filter = self.client.with_default_filter(params.filter.take());
+        params.latency_mode = params.latency_mode.or(self.client.default_latency_mode());
+        let offset = params.offset as usize;
+        params.k = k_with_offset(params.k, params.offset, MAX_TOP_DOCUMENTS_K)?;
+
+        let mut response: TopDocumentsResponse<M> = self
+            .client
+            .post_hedged("/queries/top-documents", &params, &self.options)
+            .await?;
+        response.results.drain(..offset.min(response.results.len()));
+        Ok(response)
     }
 
     /// Search for top pages matching a query
@@ -81,29 +152,43 @@ impl<'a> Queries<'a> {
         include_content: Option<bool>,
         latency_mode: Option<LatencyMode>,
     ) -> Result<TopPagesResponse> {
-        #[derive(Serialize)]
-        struct Request {
-            collection_name: String,
-            query: String,
-            k: u32,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            filter: Option<Filter>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            include_content: Option<bool>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            latency_mode: Option<LatencyMode>,
-        }
-
-        let body = Request {
+        let params = TopPagesParams {
             collection_name: collection_name.into(),
             query: query.into(),
             k,
             filter,
             include_content,
             latency_mode,
+            offset: 0,
         };
+        self.top_pages_with(params).await
+    }
 
-        self.client.post("/queries/top-pages", &body).await
+    /// Search for top pages, with optional parameters set through
+    /// [`TopPagesParams`]
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, TopPagesParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let params = TopPagesParams::new("my_collection", "what is rust", 10).include_content(true);
+    /// let results = client.queries().top_pages_with(params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn top_pages_with(&self, mut params: TopPagesParams) -> Result<TopPagesResponse> {
+        params.filter = self.client.with_default_filter(params.filter.take());
+        params.latency_mode = params.latency_mode.or(self.client.default_latency_mode());
+        let offset = params.offset as usize;
+        params.k = k_with_offset(params.k, params.offset, MAX_TOP_PAGES_K)?;
+
+        let mut response: TopPagesResponse = self
+            .client
+            .post_hedged("/queries/top-pages", &params, &self.options)
+            .await?;
+        response.results.drain(..offset.min(response.results.len()));
+        Ok(response)
     }
 
     /// Search for top snippets matching a query
@@ -115,7 +200,8 @@ impl<'a> Queries<'a> {
     /// * `filter` - Optional metadata filter
     /// * `include_document_metadata` - Whether to include document metadata
     /// * `precise_responses` - Longer snippets (around 2000 chars vs 200 chars)
-    /// * `reranker` - Optional reranker model ID
+    /// * `reranker` - Optional reranker model
+    #[allow(clippy::too_many_arguments)]
     pub async fn top_snippets(
         &self,
         collection_name: impl Into<String>,
@@ -124,24 +210,9 @@ impl<'a> Queries<'a> {
         filter: Option<Filter>,
         include_document_metadata: Option<bool>,
         precise_responses: Option<bool>,
-        reranker: Option<String>,
+        reranker: Option<Reranker>,
     ) -> Result<TopSnippetsResponse> {
-        #[derive(Serialize)]
-        struct Request {
-            collection_name: String,
-            query: String,
-            k: u32,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            filter: Option<Filter>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            include_document_metadata: Option<bool>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            precise_responses: Option<bool>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            reranker: Option<String>,
-        }
-
-        let body = Request {
+        let params = TopSnippetsParams {
             collection_name: collection_name.into(),
             query: query.into(),
             k,
@@ -149,8 +220,243 @@ impl<'a> Queries<'a> {
             include_document_metadata,
             precise_responses,
             reranker,
+            offset: 0,
         };
+        self.top_snippets_typed(params).await
+    }
+
+    /// Search for top snippets, with optional parameters set through
+    /// [`TopSnippetsParams`]
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use zeroentropy_community::{Client, TopSnippetsParams};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::from_env()?;
+    /// let results = client
+    ///     .queries()
+    ///     .top_snippets_with(TopSnippetsParams::new("my_collection", "what is rust", 10).precise(true))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn top_snippets_with(&self, params: TopSnippetsParams) -> Result<TopSnippetsResponse> {
+        self.top_snippets_typed(params).await
+    }
+
+    pub(crate) async fn top_snippets_typed<M: DeserializeOwned>(
+        &self,
+        mut params: TopSnippetsParams,
+    ) -> Result<TopSnippetsResponse<M>> {
+        params.filter = self.client.with_default_filter(params.filter.take());
+        let offset = params.offset as usize;
+        params.k = k_with_offset(params.k, params.offset, MAX_TOP_SNIPPETS_K)?;
+
+        let mut response: TopSnippetsResponse<M> = self
+            .client
+            .post_coalesced("/queries/top-snippets", &params, &self.options)
+            .await?;
+        response.results.drain(..offset.min(response.results.len()));
+        Ok(response)
+    }
+}
 
-        self.client.post("/queries/top-snippets", &body).await
+/// A search of one collection, built up fluently and run by the method
+/// naming the kind of results wanted
+///
+/// Created by [`Client::search`](crate::Client::search) or
+/// [`Queries::search`]. Returns 10 results unless [`k`](Self::k) is set.
+///
+/// The run fails with [`Error::InvalidParameter`] before anything is sent
+/// when no [`query`](Self::query) was given, or when an option was set
+/// that does not apply to the kind of results asked for:
+///
+/// - [`documents`](Self::documents) takes every option but
+///   [`precise`](Self::precise)
+/// - [`pages`](Self::pages) takes neither [`include_metadata`](Self::include_metadata),
+///   [`reranker`](Self::reranker) nor [`precise`](Self::precise)
+/// - [`snippets`](Self::snippets) takes neither
+///   [`include_content`](Self::include_content) nor
+///   [`latency_mode`](Self::latency_mode)
+///
+/// # Example
+/// ```no_run
+/// # use zeroentropy_community::{Client, Filter};
+/// # async fn example(filter: Filter) -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::from_env()?;
+/// let snippets = client
+///     .search("my_collection")
+///     .query("what is rust")
+///     .k(10)
+///     .filter(filter)
+///     .snippets()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Search<'a> {
+    queries: Queries<'a>,
+    collection_name: String,
+    query: String,
+    k: u32,
+    filter: Option<Filter>,
+    include_metadata: Option<bool>,
+    include_content: Option<bool>,
+    latency_mode: Option<LatencyMode>,
+    reranker: Option<Reranker>,
+    precise: Option<bool>,
+    offset: u32,
+}
+
+impl<'a> Search<'a> {
+    /// Search for `query`
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Return `k` results
+    pub fn k(mut self, k: u32) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Only search documents whose metadata matches `filter`
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set whether results include their document's metadata
+    pub fn include_metadata(mut self, include: bool) -> Self {
+        self.include_metadata = Some(include);
+        self
+    }
+
+    /// Set whether document and page results include their content
+    pub fn include_content(mut self, include: bool) -> Self {
+        self.include_content = Some(include);
+        self
+    }
+
+    /// Trade latency against quality for document and page results
+    pub fn latency_mode(mut self, latency_mode: LatencyMode) -> Self {
+        self.latency_mode = Some(latency_mode);
+        self
+    }
+
+    /// Rerank document and snippet results with the given model
+    pub fn reranker(mut self, reranker: impl Into<Reranker>) -> Self {
+        self.reranker = Some(reranker.into());
+        self
+    }
+
+    /// Set whether to return longer snippets
+    pub fn precise(mut self, precise: bool) -> Self {
+        self.precise = Some(precise);
+        self
+    }
+
+    /// Skip the first `offset` results, e.g. the ones already shown
+    ///
+    /// `k + offset` may not exceed the query's limit:
+    /// [`MAX_TOP_DOCUMENTS_K`], [`MAX_TOP_PAGES_K`] or
+    /// [`MAX_TOP_SNIPPETS_K`].
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Run the search for the best matching documents
+    pub async fn documents(self) -> Result<TopDocumentsResponse> {
+        self.check("document", &[("precise", self.precise.is_some())])?;
+        let params = TopDocumentsParams {
+            collection_name: self.collection_name,
+            query: self.query,
+            k: self.k,
+            filter: self.filter,
+            include_metadata: self.include_metadata,
+            include_content: self.include_content,
+            latency_mode: self.latency_mode,
+            reranker: self.reranker,
+            offset: self.offset,
+        };
+        self.queries.top_documents_typed(params).await
+    }
+
+    /// Run the search for the best matching pages
+    pub async fn pages(self) -> Result<TopPagesResponse> {
+        self.check(
+            "page",
+            &[
+                ("include_metadata", self.include_metadata.is_some()),
+                ("reranker", self.reranker.is_some()),
+                ("precise", self.precise.is_some()),
+            ],
+        )?;
+        let params = TopPagesParams {
+            collection_name: self.collection_name,
+            query: self.query,
+            k: self.k,
+            filter: self.filter,
+            include_content: self.include_content,
+            latency_mode: self.latency_mode,
+            offset: self.offset,
+        };
+        self.queries.top_pages_with(params).await
+    }
+
+    /// Run the search for the best matching snippets
+    pub async fn snippets(self) -> Result<TopSnippetsResponse> {
+        self.check(
+            "snippet",
+            &[
+                ("include_content", self.include_content.is_some()),
+                ("latency_mode", self.latency_mode.is_some()),
+            ],
+        )?;
+        let params = TopSnippetsParams {
+            collection_name: self.collection_name,
+            query: self.query,
+            k: self.k,
+            filter: self.filter,
+            include_document_metadata: self.include_metadata,
+            precise_responses: self.precise,
+            reranker: self.reranker,
+            offset: self.offset,
+        };
+        self.queries.top_snippets_typed(params).await
+    }
+
+    /// Fail unless a query was given and none of the options named in
+    /// `unsupported` was set for a `kind` search
+    fn check(&self, kind: &str, unsupported: &[(&str, bool)]) -> Result<()> {
+        if self.query.trim().is_empty() {
+            return Err(Error::InvalidParameter("search query is empty".to_string()));
+        }
+        match unsupported.iter().find(|(_, set)| *set) {
+            Some((option, _)) => Err(Error::InvalidParameter(format!(
+                "{} does not apply to {} searches",
+                option, kind
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Results to ask the API for to skip `offset` of them and return `k`
+///
+/// Queries without an offset are sent as they are and left to the API to
+/// validate.
+fn k_with_offset(k: u32, offset: u32, max: u32) -> Result<u32> {
+    if offset == 0 {
+        return Ok(k);
+    }
+    match k.checked_add(offset) {
+        Some(total) if total <= max => Ok(total),
+        _ => Err(Error::InvalidParameter(format!(
+            "k ({}) plus offset ({}) exceeds the maximum of {} results",
+            k, offset, max
+        ))),
     }
 }
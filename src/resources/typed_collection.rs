@@ -0,0 +1,244 @@
+use crate::client::{Client, RequestOptions};
+use crate::error::Result;
+use crate::resources::{Documents, Queries};
+use crate::types::{
+    CollectionIndexReport, DocumentContent, DocumentInfoListResponse, DocumentInfoResponse,
+    DocumentResponse, Filter, IndexStatus, LatencyMode, Reranker, TopDocumentsParams,
+    TopDocumentsResponse, TopSnippetsParams, TopSnippetsResponse,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// A collection bound to a metadata schema
+///
+/// Documents added through a typed collection take metadata as `M`, and
+/// document info and query results deserialize their metadata into `M`
+/// instead of the untyped [`Metadata`](crate::Metadata) map.
+///
+/// # Example
+/// ```no_run
+/// # use zeroentropy_community::Client;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Paper {
+///     title: String,
+///     arxiv_id: String,
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::from_env()?;
+/// let papers = client.typed_collection::<Paper>("papers");
+///
+/// papers.add_text(
+///     "attention.txt",
+///     "Attention is all you need",
+///     Some(Paper {
+///         title: "Attention Is All You Need".to_string(),
+///         arxiv_id: "1706.03762".to_string(),
+///     }),
+/// ).await?;
+///
+/// let results = papers.top_documents("transformers", 5, None, Some(true), None, None).await?;
+/// for result in results.results {
+///     if let Some(paper) = result.metadata {
+///         println!("{} ({})", paper.title, paper.arxiv_id);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct TypedCollection<'a, M> {
+    client: &'a Client,
+    name: String,
+    options: RequestOptions,
+    _metadata: PhantomData<fn() -> M>,
+}
+
+impl<'a, M> TypedCollection<'a, M>
+where
+    M: Serialize + DeserializeOwned,
+{
+    pub(crate) fn new(client: &'a Client, name: impl Into<String>) -> Self {
+        Self {
+            client,
+            name: name.into(),
+            options: RequestOptions::default(),
+            _metadata: PhantomData,
+        }
+    }
+
+    /// Apply request options to every call made through this handle
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Override the client timeout for calls made through this handle
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    fn documents(&self) -> Documents<'a> {
+        Documents::new(self.client).with_options(self.options.clone())
+    }
+
+    fn queries(&self) -> Queries<'a> {
+        Queries::new(self.client).with_options(self.options.clone())
+    }
+
+    /// Name of the underlying collection
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add a document to the collection
+    ///
+    /// # Arguments
+    /// * `path` - Document path/identifier
+    /// * `content` - Document content
+    /// * `metadata` - Optional typed metadata
+    /// * `overwrite` - Whether to overwrite if exists
+    pub async fn add(
+        &self,
+        path: impl Into<String>,
+        content: DocumentContent,
+        metadata: Option<M>,
+        overwrite: Option<bool>,
+    ) -> Result<DocumentResponse> {
+        self.documents()
+            .add_typed(self.name.clone(), path.into(), content, metadata, overwrite)
+            .await
+    }
+
+    /// Add a text document
+    ///
+    /// Convenience method for adding plain text documents
+    pub async fn add_text(
+        &self,
+        path: impl Into<String>,
+        text: impl Into<String>,
+        metadata: Option<M>,
+    ) -> Result<DocumentResponse> {
+        let content = DocumentContent::Text { text: text.into() };
+        self.add(path, content, metadata, None).await
+    }
+
+    /// Add a document whose text is already split into pages
+    pub async fn add_text_pages(
+        &self,
+        path: impl Into<String>,
+        pages: Vec<String>,
+        metadata: Option<M>,
+    ) -> Result<DocumentResponse> {
+        let content = DocumentContent::TextPages { pages };
+        self.add(path, content, metadata, None).await
+    }
+
+    /// Update a document's metadata or index status
+    pub async fn update(
+        &self,
+        path: impl Into<String>,
+        metadata: Option<M>,
+        index_status: Option<IndexStatus>,
+    ) -> Result<DocumentResponse> {
+        self.documents()
+            .update_typed(self.name.clone(), path.into(), metadata, index_status)
+            .await
+    }
+
+    /// Delete a document
+    pub async fn delete(&self, path: impl Into<String>) -> Result<DocumentResponse> {
+        self.documents().delete(self.name.clone(), path).await
+    }
+
+    /// Get document information with typed metadata
+    pub async fn get_info(
+        &self,
+        path: impl Into<String>,
+        include_content: Option<bool>,
+    ) -> Result<DocumentInfoResponse<M>> {
+        self.documents()
+            .get_info_typed(self.name.clone(), path.into(), include_content)
+            .await
+    }
+
+    /// Check whether a document exists
+    pub async fn exists(&self, path: impl Into<String>) -> Result<bool> {
+        self.documents().exists(self.name.clone(), path).await
+    }
+
+    /// Get list of documents with typed metadata
+    pub async fn get_info_list(
+        &self,
+        limit: Option<u32>,
+        path_gt: Option<String>,
+    ) -> Result<DocumentInfoListResponse<M>> {
+        self.documents()
+            .get_info_list_typed(self.name.clone(), limit, path_gt)
+            .await
+    }
+
+    /// Count the collection's documents by index status
+    ///
+    /// See [`Documents::index_report`].
+    pub async fn index_report(&self) -> Result<CollectionIndexReport> {
+        self.documents().index_report(self.name.clone()).await
+    }
+
+    /// Search for top documents, returning typed metadata
+    ///
+    /// See [`Queries::top_documents`] for the meaning of each argument.
+    pub async fn top_documents(
+        &self,
+        query: impl Into<String>,
+        k: u32,
+        filter: Option<Filter>,
+        include_metadata: Option<bool>,
+        latency_mode: Option<LatencyMode>,
+        reranker: Option<Reranker>,
+    ) -> Result<TopDocumentsResponse<M>> {
+        self.queries()
+            .top_documents_typed(TopDocumentsParams {
+                collection_name: self.name.clone(),
+                query: query.into(),
+                k,
+                filter,
+                include_metadata,
+                include_content: None,
+                latency_mode,
+                reranker,
+                offset: 0,
+            })
+            .await
+    }
+
+    /// Search for top snippets, returning typed document metadata
+    ///
+    /// See [`Queries::top_snippets`] for the meaning of each argument.
+    pub async fn top_snippets(
+        &self,
+        query: impl Into<String>,
+        k: u32,
+        filter: Option<Filter>,
+        include_document_metadata: Option<bool>,
+        precise_responses: Option<bool>,
+        reranker: Option<Reranker>,
+    ) -> Result<TopSnippetsResponse<M>> {
+        self.queries()
+            .top_snippets_typed(TopSnippetsParams {
+                collection_name: self.name.clone(),
+                query: query.into(),
+                k,
+                filter,
+                include_document_metadata,
+                precise_responses,
+                reranker,
+                offset: 0,
+            })
+            .await
+    }
+}
@@ -0,0 +1,126 @@
+//! Typed responses with HTTP metadata
+
+use crate::runtime::Instant;
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// Reset times above this are Unix timestamps rather than delays in seconds
+const MIN_RESET_TIMESTAMP: u64 = 1_000_000_000;
+
+/// A parsed response body together with the status and headers it came with
+///
+/// Returned by [`Client::post_with_meta`](crate::Client::post_with_meta)
+/// for callers that need request IDs or rate-limit headers for logging and
+/// metrics. Dereferences to the body.
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    /// Parsed response body
+    pub data: T,
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers; empty when the response was served from the cache
+    pub headers: HeaderMap,
+    /// ID of the request (`X-Request-Id`), as echoed by the server or as sent
+    pub request_id: Option<String>,
+}
+
+impl<T> ApiResponse<T> {
+    /// Value of a response header, if present and valid UTF-8
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// Requests allowed in the current rate-limit window (`X-RateLimit-Limit`)
+    pub fn rate_limit_limit(&self) -> Option<u64> {
+        header_u64(&self.headers, "x-ratelimit-limit")
+    }
+
+    /// Requests left in the current rate-limit window (`X-RateLimit-Remaining`)
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        header_u64(&self.headers, "x-ratelimit-remaining")
+    }
+
+    /// All rate-limit headers of the response, if it carried any
+    pub fn rate_limit(&self) -> Option<RateLimitStatus> {
+        RateLimitStatus::from_headers(&self.headers)
+    }
+
+    /// Discard the metadata and keep the body
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    /// Transform the body, keeping the metadata
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ApiResponse<U> {
+        ApiResponse {
+            data: f(self.data),
+            status: self.status,
+            headers: self.headers,
+            request_id: self.request_id,
+        }
+    }
+}
+
+impl<T> std::ops::Deref for ApiResponse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+/// Rate-limit state reported by the API through `X-RateLimit-*` headers
+///
+/// See [`Client::rate_limit_status`](crate::Client::rate_limit_status).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Requests allowed per window (`X-RateLimit-Limit`)
+    pub limit: Option<u64>,
+    /// Requests left in the current window (`X-RateLimit-Remaining`)
+    pub remaining: Option<u64>,
+    /// When the window resets (`X-RateLimit-Reset`, in seconds from now or
+    /// as a Unix timestamp)
+    ///
+    /// On native targets this is a `std::time::Instant`; on wasm32 it is
+    /// `web_time::Instant`.
+    pub reset_at: Option<Instant>,
+}
+
+impl RateLimitStatus {
+    /// Time left until the window resets; zero once it has
+    pub fn reset_in(&self) -> Option<Duration> {
+        self.reset_at.map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether the last response reported no requests left in a window
+    /// that has not reset yet
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0) && self.reset_in().is_none_or(|d| !d.is_zero())
+    }
+
+    /// Read the rate-limit headers of a response, if it carries any
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let status = Self {
+            limit: header_u64(headers, "x-ratelimit-limit"),
+            remaining: header_u64(headers, "x-ratelimit-remaining"),
+            reset_at: header_u64(headers, "x-ratelimit-reset").and_then(reset_at),
+        };
+        (status.limit.is_some() || status.remaining.is_some() || status.reset_at.is_some())
+            .then_some(status)
+    }
+}
+
+/// Convert an `X-RateLimit-Reset` value into an instant
+fn reset_at(value: u64) -> Option<Instant> {
+    if value < MIN_RESET_TIMESTAMP {
+        return Some(Instant::now() + Duration::from_secs(value));
+    }
+    let now = web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .ok()?;
+    Some(Instant::now() + Duration::from_secs(value).saturating_sub(now))
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
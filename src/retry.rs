@@ -0,0 +1,216 @@
+//! Retry policies
+//!
+//! A [`RetryPolicy`] decides, after each failed attempt, whether a call is
+//! retried and how long the client waits first. The default is an
+//! [`ExponentialBackoff`] built from
+//! [`ClientBuilder::max_retries`](crate::ClientBuilder::max_retries) and
+//! [`ClientBuilder::retryable_statuses`](crate::ClientBuilder::retryable_statuses);
+//! install another with
+//! [`ClientBuilder::retry_policy`](crate::ClientBuilder::retry_policy).
+//!
+//! Policies are not consulted for writes unless
+//! [`ClientBuilder::retry_writes`](crate::ClientBuilder::retry_writes) is
+//! enabled, except after a 429.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use zeroentropy_community::{Client, Error, RetryPolicy};
+//!
+//! /// Retry rate limiting only, once a second, forever
+//! struct UntilAdmitted;
+//!
+//! impl RetryPolicy for UntilAdmitted {
+//!     fn should_retry(&self, _attempt: u32, error: &Error) -> Option<Duration> {
+//!         matches!(error, Error::RateLimitExceeded(_)).then(|| Duration::from_secs(1))
+//!     }
+//! }
+//!
+//! let client = Client::builder()
+//!     .api_key("test-key")
+//!     .retry_policy(UntilAdmitted)
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::client::{DEFAULT_MAX_RETRIES, DEFAULT_RETRYABLE_STATUSES};
+use crate::error::Error;
+use std::time::Duration;
+
+/// Decides whether and when a failed attempt is retried
+pub trait RetryPolicy: Send + Sync {
+    /// Return the delay before retry number `attempt` (starting at 1) of a
+    /// call whose last attempt failed with `error`, or `None` to give up
+    ///
+    /// `error` is either an API error response or a transport failure.
+    fn should_retry(&self, attempt: u32, error: &Error) -> Option<Duration>;
+}
+
+/// Randomization applied to backoff delays
+///
+/// Spreads out the retries of many clients that failed at the same time,
+/// e.g. workers all rate limited by the same 429, so they do not hit the
+/// API again in lockstep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// Wait exactly the computed delay
+    None,
+    /// Wait a random delay between zero and the computed delay
+    #[default]
+    Full,
+    /// Wait a random delay between the initial delay and three times the
+    /// computed delay of the previous retry, capped at the maximum delay
+    ///
+    /// Grows about as fast as the plain backoff while keeping a floor.
+    Decorrelated,
+}
+
+/// Retry with exponentially growing delays
+///
+/// Waits up to 500ms before the first retry and doubles the delay for each
+/// following one, up to 8s, with [`Jitter::Full`] applied. Only API errors
+/// with a retryable status are retried; a `Retry-After` header on 429 and
+/// 503 responses takes the place of the computed delay.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    max_retries: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter: Jitter,
+    retryable_statuses: Vec<u16>,
+}
+
+impl ExponentialBackoff {
+    /// Retry up to `max_retries` times with the default delays and statuses
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            jitter: Jitter::default(),
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+        }
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Set the longest delay between two attempts
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set how delays are randomized
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the response status codes that are retried
+    pub fn retryable_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Delay before retry number `attempt` without jitter
+    fn base_delay(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RETRIES)
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn should_retry(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt > self.max_retries || !has_status(error, &self.retryable_statuses) {
+            return None;
+        }
+        if let Some(delay) = retry_after(error) {
+            return Some(delay);
+        }
+        let delay = match self.jitter {
+            Jitter::None => self.base_delay(attempt),
+            Jitter::Full => random_between(Duration::ZERO, self.base_delay(attempt)),
+            Jitter::Decorrelated => {
+                let previous = match attempt {
+                    1 => self.initial_delay,
+                    _ => self.base_delay(attempt - 1),
+                };
+                let upper = previous.saturating_mul(3).min(self.max_delay);
+                random_between(self.initial_delay.min(upper), upper)
+            }
+        };
+        Some(delay)
+    }
+}
+
+/// Retry after the same delay every time
+///
+/// Retries the same statuses as [`ExponentialBackoff`] and likewise honors
+/// `Retry-After`.
+#[derive(Debug, Clone)]
+pub struct ConstantBackoff {
+    max_retries: u32,
+    delay: Duration,
+    retryable_statuses: Vec<u16>,
+}
+
+impl ConstantBackoff {
+    /// Retry up to `max_retries` times, waiting `delay` before each retry
+    pub fn new(max_retries: u32, delay: Duration) -> Self {
+        Self {
+            max_retries,
+            delay,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+        }
+    }
+
+    /// Set the response status codes that are retried
+    pub fn retryable_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+}
+
+impl RetryPolicy for ConstantBackoff {
+    fn should_retry(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt > self.max_retries || !has_status(error, &self.retryable_statuses) {
+            return None;
+        }
+        Some(retry_after(error).unwrap_or(self.delay))
+    }
+}
+
+/// Never retry
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn should_retry(&self, _attempt: u32, _error: &Error) -> Option<Duration> {
+        None
+    }
+}
+
+/// Whether `error` is an API error with one of `statuses`
+fn has_status(error: &Error, statuses: &[u16]) -> bool {
+    error.api_error().is_some_and(|e| statuses.contains(&e.status))
+}
+
+/// Delay requested by the server through `Retry-After`
+fn retry_after(error: &Error) -> Option<Duration> {
+    error.api_error().and_then(|e| e.retry_after)
+}
+
+/// Uniformly random duration in `[low, high]`
+fn random_between(low: Duration, high: Duration) -> Duration {
+    low + (high - low).mul_f64(fastrand::f64())
+}
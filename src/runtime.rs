@@ -0,0 +1,21 @@
+//! Runtime-specific primitives
+//!
+//! Native targets run on tokio; wasm32 targets run on the host's event loop
+//! (browser or worker), where timers are provided by `setTimeout`.
+
+use std::time::Duration;
+
+/// Monotonic clock; `std::time::Instant` panics on wasm32
+pub(crate) use web_time::Instant;
+
+/// Wait for the given duration without blocking the executor
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Wait for the given duration without blocking the executor
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
@@ -0,0 +1,73 @@
+//! `tower::Service` integration
+//!
+//! [`Client`] implements `Service<ZeroEntropyRequest>`, exposing the raw
+//! request path so it can be wrapped in tower layers (rate limiting,
+//! timeouts, load shedding) alongside the rest of an application's stack.
+//!
+//! # Example
+//! ```no_run
+//! use tower_service::Service;
+//! use zeroentropy_community::{Client, ZeroEntropyRequest};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut client = Client::from_env()?;
+//! let response = client
+//!     .call(ZeroEntropyRequest::new("/collections/get-collection-list", serde_json::json!({})))
+//!     .await?;
+//! println!("{}", response);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::{Client, RequestOptions};
+use crate::error::{Error, Result};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A raw API call: endpoint path and JSON body
+#[derive(Debug, Clone)]
+pub struct ZeroEntropyRequest {
+    /// Endpoint path relative to the base URL, e.g. `/queries/top-snippets`
+    pub endpoint: String,
+    /// JSON request body
+    pub body: serde_json::Value,
+    /// Per-request options
+    pub options: RequestOptions,
+}
+
+impl ZeroEntropyRequest {
+    /// Create a request with default options
+    pub fn new(endpoint: impl Into<String>, body: serde_json::Value) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            body,
+            options: RequestOptions::default(),
+        }
+    }
+
+    /// Set the per-request options
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl tower_service::Service<ZeroEntropyRequest> for Client {
+    type Response = serde_json::Value;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<serde_json::Value>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: ZeroEntropyRequest) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move {
+            client
+                .post(&request.endpoint, &request.body, &request.options)
+                .await
+        })
+    }
+}
@@ -0,0 +1,101 @@
+//! Tracking of in-flight calls for graceful shutdown
+
+use crate::error::{Error, Result};
+use crate::runtime::{self, Instant};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Outcome of [`Client::shutdown`](crate::Client::shutdown)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Endpoints of the calls still in flight when the timeout elapsed
+    ///
+    /// Empty when everything drained in time. Abandoned calls are not
+    /// aborted; they keep running until they complete or are dropped.
+    pub abandoned: Vec<String>,
+}
+
+impl ShutdownReport {
+    /// Whether every in-flight call finished before the timeout
+    pub fn is_clean(&self) -> bool {
+        self.abandoned.is_empty()
+    }
+}
+
+/// Shared by all clones of a client
+#[derive(Default)]
+pub(crate) struct Lifecycle {
+    closed: AtomicBool,
+    next_id: AtomicU64,
+    /// Endpoints of in-flight calls by call ID
+    in_flight: Mutex<HashMap<u64, String>>,
+    drained: Notify,
+}
+
+/// Registration of an in-flight call, released on drop
+pub(crate) struct CallGuard<'a> {
+    lifecycle: &'a Lifecycle,
+    id: u64,
+}
+
+impl Lifecycle {
+    /// Register a call, failing once shutdown has begun
+    pub(crate) fn begin(&self, endpoint: &str) -> Result<CallGuard<'_>> {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(Error::ShutDown);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.calls().insert(id, endpoint.to_string());
+        Ok(CallGuard {
+            lifecycle: self,
+            id,
+        })
+    }
+
+    /// Stop accepting calls and wait up to `timeout` for in-flight ones
+    pub(crate) async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        use futures_util::future;
+
+        self.closed.store(true, Ordering::SeqCst);
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Register for the wakeup before checking to not miss it
+            let drained = self.drained.notified();
+            let remaining = {
+                let calls = self.calls();
+                if calls.is_empty() {
+                    return ShutdownReport::default();
+                }
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    let mut abandoned: Vec<_> = calls.iter().collect();
+                    abandoned.sort_by_key(|(id, _)| **id);
+                    return ShutdownReport {
+                        abandoned: abandoned.into_iter().map(|(_, e)| e.clone()).collect(),
+                    };
+                }
+                remaining
+            };
+
+            let sleep = runtime::sleep(remaining);
+            future::select(std::pin::pin!(drained), std::pin::pin!(sleep)).await;
+        }
+    }
+
+    fn calls(&self) -> std::sync::MutexGuard<'_, HashMap<u64, String>> {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for CallGuard<'_> {
+    fn drop(&mut self) {
+        let mut calls = self.lifecycle.calls();
+        calls.remove(&self.id);
+        if calls.is_empty() {
+            self.lifecycle.drained.notify_waiters();
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Request counters kept by the client
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Snapshot of a client's request counters
+///
+/// Counts every attempt sent, so a call that was retried twice counts as
+/// three requests. Shared by all clones of a client. See
+/// [`Client::stats`](crate::Client::stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Requests sent per endpoint path, e.g. `/queries/top-documents`
+    pub requests_by_endpoint: HashMap<String, u64>,
+    /// Error responses received per HTTP status code
+    pub errors_by_status: HashMap<u16, u64>,
+    /// Requests that failed without a response
+    pub transport_errors: u64,
+    /// Retries made after a failed attempt
+    pub retries: u64,
+    /// Request body bytes sent, after compression
+    pub bytes_sent: u64,
+}
+
+impl ClientStats {
+    /// Total requests sent across all endpoints
+    pub fn requests(&self) -> u64 {
+        self.requests_by_endpoint.values().sum()
+    }
+
+    /// Total error responses received across all statuses
+    pub fn errors(&self) -> u64 {
+        self.errors_by_status.values().sum()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StatsCounters {
+    requests_by_endpoint: Mutex<HashMap<String, u64>>,
+    errors_by_status: Mutex<HashMap<u16, u64>>,
+    transport_errors: AtomicU64,
+    retries: AtomicU64,
+    bytes_sent: AtomicU64,
+}
+
+impl StatsCounters {
+    /// Count a request sent to `endpoint` and its outcome: the response
+    /// status, or `None` when no response arrived
+    pub(crate) fn record_request(&self, endpoint: &str, body_bytes: usize, status: Option<u16>) {
+        *self
+            .requests_by_endpoint
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(endpoint.to_string())
+            .or_default() += 1;
+        self.bytes_sent.fetch_add(body_bytes as u64, Ordering::Relaxed);
+        match status {
+            Some(status) if status >= 400 => {
+                *self
+                    .errors_by_status
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .entry(status)
+                    .or_default() += 1;
+            }
+            Some(_) => {}
+            None => {
+                self.transport_errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            requests_by_endpoint: self
+                .requests_by_endpoint
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            errors_by_status: self
+                .errors_by_status
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            transport_errors: self.transport_errors.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+}
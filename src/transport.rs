@@ -0,0 +1,221 @@
+//! Pluggable HTTP transport
+//!
+//! The client builds fully-formed [`HttpRequest`]s (URL, headers, encoded
+//! body) and hands them to an [`HttpTransport`], which only has to perform
+//! the exchange. The default transport is [`ReqwestTransport`]; implement the
+//! trait to use another HTTP stack or a test double.
+//!
+//! # Example
+//! ```
+//! use zeroentropy_community::{async_trait, Client, HttpRequest, HttpResponse, HttpTransport};
+//!
+//! struct AlwaysEmpty;
+//!
+//! #[async_trait]
+//! impl HttpTransport for AlwaysEmpty {
+//!     async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+//!         Ok(HttpResponse {
+//!             status: 200,
+//!             headers: Default::default(),
+//!             body: br#"{"collections": []}"#.to_vec(),
+//!         })
+//!     }
+//! }
+//!
+//! let client = Client::builder()
+//!     .api_key("test-key")
+//!     .transport(AlwaysEmpty)
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::error::Result;
+#[cfg(feature = "middleware")]
+use crate::error::Error;
+#[cfg(not(target_arch = "wasm32"))]
+use futures_util::stream::{self, BoxStream, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use std::time::Duration;
+
+pub use async_trait::async_trait;
+
+/// An HTTP request ready to be sent
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// Absolute URL, including any query string
+    pub url: String,
+    pub headers: HeaderMap,
+    /// Encoded request body (compressed when `Content-Encoding` is set)
+    pub body: Vec<u8>,
+    /// Timeout for this exchange, if one applies
+    pub timeout: Option<Duration>,
+}
+
+/// An HTTP response returned by a transport
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    /// Body as received; decoding `Content-Encoding` is left to the client
+    pub body: Vec<u8>,
+}
+
+/// Response body delivered in chunks as it arrives
+#[cfg(not(target_arch = "wasm32"))]
+pub type BodyStream = BoxStream<'static, Result<Vec<u8>>>;
+
+/// An HTTP response whose body has not been read yet
+#[cfg(not(target_arch = "wasm32"))]
+pub struct HttpStreamResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: BodyStream,
+}
+
+/// Performs HTTP exchanges on behalf of the client
+///
+/// Transport-level failures should be reported as [`Error::Http`] or
+/// [`Error::Transport`]; non-2xx responses are returned as `Ok` and
+/// interpreted by the client.
+///
+/// [`Error::Http`]: crate::Error::Http
+/// [`Error::Transport`]: crate::Error::Transport
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait HttpTransport: Send + Sync {
+    /// Send a request and return the raw response
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+
+    /// Send a request and return the response before reading its body
+    ///
+    /// Used for downloads that should not be buffered in memory. The
+    /// default implementation delivers the body of [`send`](Self::send) as
+    /// a single chunk.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send_streaming(&self, request: HttpRequest) -> Result<HttpStreamResponse> {
+        let response = self.send(request).await?;
+        Ok(HttpStreamResponse {
+            status: response.status,
+            headers: response.headers,
+            body: stream::once(async move { Ok(response.body) }).boxed(),
+        })
+    }
+}
+
+/// Default transport backed by a `reqwest::Client`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Wrap a configured `reqwest::Client`
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut builder = self
+            .client
+            .request(request.method, &request.url)
+            .headers(request.headers)
+            .body(request.body);
+
+        // The fetch API used on wasm32 has no request timeouts
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send_streaming(&self, request: HttpRequest) -> Result<HttpStreamResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, &request.url)
+            .headers(request.headers)
+            .body(request.body);
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let response = builder.send().await?;
+        Ok(HttpStreamResponse {
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            body: response
+                .bytes_stream()
+                .map(|chunk| Ok(chunk?.to_vec()))
+                .boxed(),
+        })
+    }
+}
+
+/// Transport backed by a `reqwest_middleware::ClientWithMiddleware`
+///
+/// Lets existing middleware stacks (tracing, caching, custom retries)
+/// handle every request made by the SDK.
+#[cfg(feature = "middleware")]
+#[derive(Clone)]
+pub struct MiddlewareTransport {
+    client: reqwest_middleware::ClientWithMiddleware,
+}
+
+#[cfg(feature = "middleware")]
+impl MiddlewareTransport {
+    /// Wrap a client with middleware
+    pub fn new(client: reqwest_middleware::ClientWithMiddleware) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "middleware")]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl HttpTransport for MiddlewareTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut builder = self
+            .client
+            .request(request.method, &request.url)
+            .headers(request.headers)
+            .body(request.body);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let response = builder.send().await.map_err(|e| match e {
+            reqwest_middleware::Error::Reqwest(e) => Error::Http(e),
+            reqwest_middleware::Error::Middleware(e) => Error::Transport(e.into()),
+        })?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
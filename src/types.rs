@@ -9,32 +9,394 @@ pub enum DocumentContent {
     Text { text: String },
     /// Auto-detect format (for PDFs, images with OCR)
     Auto { base64_data: String },
+    /// Text already split into pages, e.g. OCR output; results keep the
+    /// page numbers
+    #[serde(rename = "text-pages")]
+    TextPages { pages: Vec<String> },
+}
+
+/// A document to add with [`Documents::add_batch`](crate::Documents::add_batch)
+#[derive(Debug, Clone)]
+pub struct DocumentSpec {
+    pub path: String,
+    pub content: DocumentContent,
+    pub metadata: Option<Metadata>,
+    pub overwrite: Option<bool>,
+}
+
+impl DocumentSpec {
+    /// A document with the given path and content
+    pub fn new(path: impl Into<String>, content: DocumentContent) -> Self {
+        Self {
+            path: path.into(),
+            content,
+            metadata: None,
+            overwrite: None,
+        }
+    }
+
+    /// A plain text document
+    pub fn text(path: impl Into<String>, text: impl Into<String>) -> Self {
+        Self::new(path, DocumentContent::Text { text: text.into() })
+    }
+
+    /// Set the document's metadata
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Set whether an existing document at the same path is replaced
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = Some(overwrite);
+        self
+    }
+}
+
+/// Outcome of adding one document of a batch
+#[derive(Debug)]
+pub struct BatchAddResult {
+    pub path: String,
+    pub result: crate::Result<DocumentResponse>,
+}
+
+/// Options of [`Collections::copy`](crate::Collections::copy)
+///
+/// # Example
+/// ```
+/// use zeroentropy_community::CopyOptions;
+///
+/// let options = CopyOptions::new().concurrency(4).overwrite(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CopyOptions {
+    pub(crate) concurrency: usize,
+    pub(crate) overwrite: bool,
+    pub(crate) resume_after: Option<String>,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: crate::DEFAULT_BATCH_CONCURRENCY,
+            overwrite: false,
+            resume_after: None,
+        }
+    }
+}
+
+impl CopyOptions {
+    /// Copy with the default options: documents already in the
+    /// destination are skipped, [`DEFAULT_BATCH_CONCURRENCY`](crate::DEFAULT_BATCH_CONCURRENCY)
+    /// at a time
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy up to `concurrency` documents at a time
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Replace documents that already exist in the destination instead of
+    /// skipping them
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Start after the document at `path`, e.g. the
+    /// [`CopyReport::last_path`] of an interrupted copy
+    pub fn resume_after(mut self, path: impl Into<String>) -> Self {
+        self.resume_after = Some(path.into());
+        self
+    }
+}
+
+/// Outcome of [`Collections::copy`](crate::Collections::copy)
+#[derive(Debug, Default)]
+pub struct CopyReport {
+    /// Documents written to the destination
+    pub copied: usize,
+    /// Documents skipped because the destination already had them
+    pub skipped: usize,
+    /// Documents that could not be copied, with the reason
+    pub failed: Vec<(String, crate::Error)>,
+    /// Path of the last document handled, to pass to
+    /// [`CopyOptions::resume_after`] if the copy is interrupted
+    pub last_path: Option<String>,
+}
+
+/// Outcome of [`Documents::delete_all`](crate::Documents::delete_all)
+#[derive(Debug, Default)]
+pub struct DeleteAllReport {
+    /// Documents deleted, including ones already gone when their delete
+    /// was sent
+    pub deleted: usize,
+    /// Documents that could not be deleted, with the reason
+    pub failed: Vec<(String, crate::Error)>,
+}
+
+/// Outcome of [`Documents::reindex_failed`](crate::Documents::reindex_failed)
+#[derive(Debug, Default)]
+pub struct ReindexReport {
+    /// Failed documents submitted again, which are now waiting to be
+    /// parsed and indexed
+    pub resubmitted: Vec<String>,
+    /// Failed documents that could not be submitted again, with the reason
+    pub still_failing: Vec<(String, crate::Error)>,
 }
 
 /// Metadata type for documents
 pub type Metadata = HashMap<String, MetadataValue>;
 
-/// Metadata values can be strings or arrays of strings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Metadata values can be strings, arrays of strings, numbers or booleans
+///
+/// Whole numbers deserialize as [`Integer`](MetadataValue::Integer) and
+/// other numbers as [`Number`](MetadataValue::Number). Variants may be
+/// added by crate features or in minor releases, so matches need a
+/// wildcard arm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
+#[non_exhaustive]
 pub enum MetadataValue {
     String(String),
     Array(Vec<String>),
+    Integer(i64),
+    Number(f64),
+    Bool(bool),
+    /// A point in time, sent as an RFC 3339 UTC timestamp with second
+    /// precision, e.g. `2024-05-01T12:00:00Z`
+    ///
+    /// The API stores timestamps as strings, so they come back as
+    /// [`String`](MetadataValue::String); read them with
+    /// [`as_datetime`](MetadataValue::as_datetime).
+    #[cfg(feature = "chrono")]
+    #[serde(serialize_with = "serialize_datetime")]
+    DateTime(chrono::DateTime<chrono::Utc>),
+}
+
+#[cfg(feature = "chrono")]
+impl MetadataValue {
+    /// The timestamp held by a [`DateTime`](MetadataValue::DateTime) or
+    /// by an RFC 3339 [`String`](MetadataValue::String)
+    pub fn as_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            MetadataValue::DateTime(datetime) => Some(*datetime),
+            MetadataValue::String(value) => chrono::DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|datetime| datetime.with_timezone(&chrono::Utc)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for MetadataValue {
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        MetadataValue::DateTime(datetime)
+    }
+}
+
+/// Timestamps in one fixed format, so comparing them as strings orders
+/// them in time
+#[cfg(feature = "chrono")]
+fn format_datetime(datetime: &chrono::DateTime<chrono::Utc>) -> String {
+    datetime.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(feature = "chrono")]
+fn serialize_datetime<S: serde::Serializer>(
+    datetime: &chrono::DateTime<chrono::Utc>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format_datetime(datetime))
+}
+
+impl From<&str> for MetadataValue {
+    fn from(value: &str) -> Self {
+        MetadataValue::String(value.to_string())
+    }
+}
+
+impl From<String> for MetadataValue {
+    fn from(value: String) -> Self {
+        MetadataValue::String(value)
+    }
+}
+
+impl From<Vec<String>> for MetadataValue {
+    fn from(values: Vec<String>) -> Self {
+        MetadataValue::Array(values)
+    }
+}
+
+impl From<i64> for MetadataValue {
+    fn from(value: i64) -> Self {
+        MetadataValue::Integer(value)
+    }
+}
+
+impl From<f64> for MetadataValue {
+    fn from(value: f64) -> Self {
+        MetadataValue::Number(value)
+    }
+}
+
+impl From<bool> for MetadataValue {
+    fn from(value: bool) -> Self {
+        MetadataValue::Bool(value)
+    }
 }
 
 /// Filter for querying documents
 pub type Filter = HashMap<String, serde_json::Value>;
 
+/// Filters on timestamp metadata written as
+/// [`MetadataValue::DateTime`]
+///
+/// # Example
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use zeroentropy_community::{DateFilter, Filter};
+///
+/// let filter = Filter::date_between(
+///     "published",
+///     Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).unwrap(),
+/// );
+/// ```
+#[cfg(feature = "chrono")]
+pub trait DateFilter: Sized {
+    /// Match documents whose `field` lies between `start` and `end`,
+    /// both included
+    fn date_between(
+        field: impl Into<String>,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Self;
+
+    /// Match documents whose `field` is later than `time`
+    fn date_after(field: impl Into<String>, time: chrono::DateTime<chrono::Utc>) -> Self;
+
+    /// Match documents whose `field` is earlier than `time`
+    fn date_before(field: impl Into<String>, time: chrono::DateTime<chrono::Utc>) -> Self;
+}
+
+#[cfg(feature = "chrono")]
+impl DateFilter for Filter {
+    fn date_between(
+        field: impl Into<String>,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let condition = serde_json::json!({
+            "$gte": format_datetime(&start),
+            "$lte": format_datetime(&end),
+        });
+        Filter::from([(field.into(), condition)])
+    }
+
+    fn date_after(field: impl Into<String>, time: chrono::DateTime<chrono::Utc>) -> Self {
+        let condition = serde_json::json!({ "$gt": format_datetime(&time) });
+        Filter::from([(field.into(), condition)])
+    }
+
+    fn date_before(field: impl Into<String>, time: chrono::DateTime<chrono::Utc>) -> Self {
+        let condition = serde_json::json!({ "$lt": format_datetime(&time) });
+        Filter::from([(field.into(), condition)])
+    }
+}
+
 /// Latency mode for queries
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+///
+/// Modes added to the API after this version of the crate deserialize as
+/// [`LatencyMode::Default`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum LatencyMode {
     Low,
     High,
+    /// Let the API pick its default mode; the mode is left out of requests
+    #[default]
+    #[serde(other)]
+    Default,
+}
+
+impl LatencyMode {
+    /// Whether a query's mode is left out of the request
+    pub(crate) fn is_unset(mode: &Option<LatencyMode>) -> bool {
+        matches!(mode, None | Some(LatencyMode::Default))
+    }
+}
+
+/// A ZeroEntropy reranker model, for rerank calls and query reranking
+///
+/// Models released after this version of the crate can be named with
+/// [`Reranker::Custom`]; a plain model ID converts into a `Reranker`, so
+/// `"zerank-1"` and `Reranker::Zerank1` are interchangeable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum Reranker {
+    /// `zerank-1`
+    Zerank1,
+    /// `zerank-1-small`, faster and cheaper than `zerank-1`
+    Zerank1Small,
+    /// `zerank-2`
+    Zerank2,
+    /// Any other model ID
+    Custom(String),
+}
+
+impl Reranker {
+    /// The model ID sent to the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Reranker::Zerank1 => "zerank-1",
+            Reranker::Zerank1Small => "zerank-1-small",
+            Reranker::Zerank2 => "zerank-2",
+            Reranker::Custom(model_id) => model_id,
+        }
+    }
+}
+
+impl std::fmt::Display for Reranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for Reranker {
+    fn from(model_id: String) -> Self {
+        match model_id.as_str() {
+            "zerank-1" => Reranker::Zerank1,
+            "zerank-1-small" => Reranker::Zerank1Small,
+            "zerank-2" => Reranker::Zerank2,
+            _ => Reranker::Custom(model_id),
+        }
+    }
+}
+
+impl From<&str> for Reranker {
+    fn from(model_id: &str) -> Self {
+        Reranker::from(model_id.to_string())
+    }
+}
+
+impl From<Reranker> for String {
+    fn from(reranker: Reranker) -> Self {
+        match reranker {
+            Reranker::Custom(model_id) => model_id,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 /// Index status for documents
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum IndexStatus {
     NotParsed,
@@ -46,6 +408,13 @@ pub enum IndexStatus {
     Indexed,
 }
 
+impl IndexStatus {
+    /// Whether the document failed to parse or index
+    pub fn is_failed(self) -> bool {
+        matches!(self, IndexStatus::ParsingFailed | IndexStatus::IndexingFailed)
+    }
+}
+
 /// Response from status endpoint
 #[derive(Debug, Deserialize)]
 pub struct StatusResponse {
@@ -72,26 +441,108 @@ pub struct DocumentResponse {
 }
 
 /// Document information
+///
+/// Generic over the metadata type so typed collections can expose their own
+/// metadata schema; defaults to the untyped [`Metadata`] map.
 #[derive(Debug, Deserialize)]
-pub struct DocumentInfo {
+pub struct DocumentInfo<M = Metadata> {
     pub path: String,
     pub index_status: IndexStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<Metadata>,
+    pub metadata: Option<M>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<DocumentContent>,
+    /// Signed URL of the original file, for opening it in a browser
+    #[serde(default)]
+    pub file_url: Option<String>,
+    /// When `file_url` stops working, as an RFC 3339 timestamp; get a new
+    /// URL with [`Documents::refresh_file_url`](crate::Documents::refresh_file_url)
+    #[serde(default)]
+    pub file_url_expires_at: Option<String>,
+    /// Why parsing or indexing failed, when the API reports it
+    #[serde(default)]
+    pub failure_reason: Option<String>,
+}
+
+/// Number of documents in each index status across a collection
+///
+/// Returned by [`Documents::index_report`](crate::Documents::index_report).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectionIndexReport {
+    pub collection_name: String,
+    pub not_parsed: u64,
+    pub not_indexed: u64,
+    pub parsing: u64,
+    pub parsing_failed: u64,
+    pub indexing: u64,
+    pub indexing_failed: u64,
+    pub indexed: u64,
+}
+
+impl CollectionIndexReport {
+    /// Number of documents in `status`
+    pub fn count(&self, status: IndexStatus) -> u64 {
+        match status {
+            IndexStatus::NotParsed => self.not_parsed,
+            IndexStatus::NotIndexed => self.not_indexed,
+            IndexStatus::Parsing => self.parsing,
+            IndexStatus::ParsingFailed => self.parsing_failed,
+            IndexStatus::Indexing => self.indexing,
+            IndexStatus::IndexingFailed => self.indexing_failed,
+            IndexStatus::Indexed => self.indexed,
+        }
+    }
+
+    /// Number of documents in the collection
+    pub fn total(&self) -> u64 {
+        self.not_parsed
+            + self.not_indexed
+            + self.parsing
+            + self.parsing_failed
+            + self.indexing
+            + self.indexing_failed
+            + self.indexed
+    }
+
+    /// Documents that failed to parse or index
+    pub fn failed(&self) -> u64 {
+        self.parsing_failed + self.indexing_failed
+    }
+
+    /// Documents still waiting to be parsed or indexed
+    pub fn pending(&self) -> u64 {
+        self.not_parsed + self.not_indexed + self.parsing + self.indexing
+    }
+
+    /// Whether no document is waiting to be parsed or indexed
+    pub fn is_settled(&self) -> bool {
+        self.pending() == 0
+    }
+
+    pub(crate) fn add(&mut self, status: IndexStatus) {
+        let count = match status {
+            IndexStatus::NotParsed => &mut self.not_parsed,
+            IndexStatus::NotIndexed => &mut self.not_indexed,
+            IndexStatus::Parsing => &mut self.parsing,
+            IndexStatus::ParsingFailed => &mut self.parsing_failed,
+            IndexStatus::Indexing => &mut self.indexing,
+            IndexStatus::IndexingFailed => &mut self.indexing_failed,
+            IndexStatus::Indexed => &mut self.indexed,
+        };
+        *count += 1;
+    }
 }
 
 /// Response from get document info
 #[derive(Debug, Deserialize)]
-pub struct DocumentInfoResponse {
-    pub document: DocumentInfo,
+pub struct DocumentInfoResponse<M = Metadata> {
+    pub document: DocumentInfo<M>,
 }
 
 /// Response from get document info list
 #[derive(Debug, Deserialize)]
-pub struct DocumentInfoListResponse {
-    pub documents: Vec<DocumentInfo>,
+pub struct DocumentInfoListResponse<M = Metadata> {
+    pub documents: Vec<DocumentInfo<M>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path_gt: Option<String>,
 }
@@ -113,17 +564,24 @@ pub struct PageInfoResponse {
 
 /// Query result for top documents
 #[derive(Debug, Deserialize)]
-pub struct DocumentResult {
+pub struct DocumentResult<M = Metadata> {
     pub path: String,
     pub score: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<Metadata>,
+    pub metadata: Option<M>,
+    /// Document text, when requested with
+    /// [`TopDocumentsParams::include_content`]
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Signed URL of the original file, when the API provides one
+    #[serde(default)]
+    pub file_url: Option<String>,
 }
 
 /// Response from top documents query
 #[derive(Debug, Deserialize)]
-pub struct TopDocumentsResponse {
-    pub results: Vec<DocumentResult>,
+pub struct TopDocumentsResponse<M = Metadata> {
+    pub results: Vec<DocumentResult<M>>,
 }
 
 /// Query result for top pages
@@ -144,28 +602,361 @@ pub struct TopPagesResponse {
 
 /// Query result for top snippets
 #[derive(Debug, Deserialize)]
-pub struct SnippetResult {
+pub struct SnippetResult<M = Metadata> {
     pub path: String,
     pub content: String,
     pub score: f64,
     pub page_number: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<Metadata>,
+    pub metadata: Option<M>,
 }
 
 /// Response from top snippets query
 #[derive(Debug, Deserialize)]
-pub struct TopSnippetsResponse {
-    pub results: Vec<SnippetResult>,
+pub struct TopSnippetsResponse<M = Metadata> {
+    pub results: Vec<SnippetResult<M>>,
+}
+
+/// Parameters of a top documents query
+///
+/// # Example
+/// ```
+/// use zeroentropy_community::{LatencyMode, TopDocumentsParams};
+///
+/// let params = TopDocumentsParams::new("my_collection", "what is rust", 10)
+///     .include_metadata(true)
+///     .latency_mode(LatencyMode::Low);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct TopDocumentsParams {
+    pub(crate) collection_name: String,
+    pub(crate) query: String,
+    pub(crate) k: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) include_metadata: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) include_content: Option<bool>,
+    #[serde(skip_serializing_if = "LatencyMode::is_unset")]
+    pub(crate) latency_mode: Option<LatencyMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) reranker: Option<Reranker>,
+    #[serde(skip)]
+    pub(crate) offset: u32,
+}
+
+impl TopDocumentsParams {
+    /// Search `collection_name` for the `k` documents best matching `query`
+    pub fn new(collection_name: impl Into<String>, query: impl Into<String>, k: u32) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            query: query.into(),
+            k,
+            filter: None,
+            include_metadata: None,
+            include_content: None,
+            latency_mode: None,
+            reranker: None,
+            offset: 0,
+        }
+    }
+
+    /// Only search documents whose metadata matches `filter`
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set whether results include their metadata
+    pub fn include_metadata(mut self, include: bool) -> Self {
+        self.include_metadata = Some(include);
+        self
+    }
+
+    /// Set whether results include the document text, so a result list
+    /// can be displayed without fetching each document
+    pub fn include_content(mut self, include: bool) -> Self {
+        self.include_content = Some(include);
+        self
+    }
+
+    /// Trade latency against quality
+    pub fn latency_mode(mut self, latency_mode: LatencyMode) -> Self {
+        self.latency_mode = Some(latency_mode);
+        self
+    }
+
+    /// Rerank the results with the given model
+    pub fn reranker(mut self, reranker: impl Into<Reranker>) -> Self {
+        self.reranker = Some(reranker.into());
+        self
+    }
+
+    /// Skip the first `offset` results, e.g. the ones already shown
+    ///
+    /// The API has no offset of its own, so this asks for `k + offset`
+    /// results and drops the first `offset`. Keep every other parameter the
+    /// same as the earlier query so the skipped results are the ones that
+    /// query returned. `k + offset` may not exceed
+    /// [`MAX_TOP_DOCUMENTS_K`](crate::MAX_TOP_DOCUMENTS_K); larger values fail with
+    /// [`Error::InvalidParameter`](crate::Error::InvalidParameter) before
+    /// anything is sent.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Parameters of a top pages query
+///
+/// # Example
+/// ```
+/// use zeroentropy_community::TopPagesParams;
+///
+/// let params = TopPagesParams::new("my_collection", "what is rust", 10).include_content(true);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct TopPagesParams {
+    pub(crate) collection_name: String,
+    pub(crate) query: String,
+    pub(crate) k: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) include_content: Option<bool>,
+    #[serde(skip_serializing_if = "LatencyMode::is_unset")]
+    pub(crate) latency_mode: Option<LatencyMode>,
+    #[serde(skip)]
+    pub(crate) offset: u32,
+}
+
+impl TopPagesParams {
+    /// Search `collection_name` for the `k` pages best matching `query`
+    pub fn new(collection_name: impl Into<String>, query: impl Into<String>, k: u32) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            query: query.into(),
+            k,
+            filter: None,
+            include_content: None,
+            latency_mode: None,
+            offset: 0,
+        }
+    }
+
+    /// Only search documents whose metadata matches `filter`
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set whether results include the page content
+    pub fn include_content(mut self, include: bool) -> Self {
+        self.include_content = Some(include);
+        self
+    }
+
+    /// Trade latency against quality
+    pub fn latency_mode(mut self, latency_mode: LatencyMode) -> Self {
+        self.latency_mode = Some(latency_mode);
+        self
+    }
+
+    /// Skip the first `offset` results, e.g. the ones already shown
+    ///
+    /// The API has no offset of its own, so this asks for `k + offset`
+    /// results and drops the first `offset`. Keep every other parameter the
+    /// same as the earlier query so the skipped results are the ones that
+    /// query returned. `k + offset` may not exceed
+    /// [`MAX_TOP_PAGES_K`](crate::MAX_TOP_PAGES_K); larger values fail with
+    /// [`Error::InvalidParameter`](crate::Error::InvalidParameter) before
+    /// anything is sent.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Parameters of a top snippets query
+///
+/// # Example
+/// ```
+/// use zeroentropy_community::TopSnippetsParams;
+///
+/// let params = TopSnippetsParams::new("my_collection", "what is rust", 10)
+///     .precise(true)
+///     .reranker("zerank-1");
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct TopSnippetsParams {
+    pub(crate) collection_name: String,
+    pub(crate) query: String,
+    pub(crate) k: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) include_document_metadata: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) precise_responses: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) reranker: Option<Reranker>,
+    #[serde(skip)]
+    pub(crate) offset: u32,
+}
+
+impl TopSnippetsParams {
+    /// Search `collection_name` for the `k` snippets best matching `query`
+    pub fn new(collection_name: impl Into<String>, query: impl Into<String>, k: u32) -> Self {
+        Self {
+            collection_name: collection_name.into(),
+            query: query.into(),
+            k,
+            filter: None,
+            include_document_metadata: None,
+            precise_responses: None,
+            reranker: None,
+            offset: 0,
+        }
+    }
+
+    /// Only search documents whose metadata matches `filter`
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set whether results include their document's metadata
+    pub fn include_document_metadata(mut self, include: bool) -> Self {
+        self.include_document_metadata = Some(include);
+        self
+    }
+
+    /// Set whether to return longer snippets (around 2000 characters
+    /// instead of 200)
+    pub fn precise(mut self, precise: bool) -> Self {
+        self.precise_responses = Some(precise);
+        self
+    }
+
+    /// Rerank the results with the given model
+    pub fn reranker(mut self, reranker: impl Into<Reranker>) -> Self {
+        self.reranker = Some(reranker.into());
+        self
+    }
+
+    /// Skip the first `offset` results, e.g. the ones already shown
+    ///
+    /// The API has no offset of its own, so this asks for `k + offset`
+    /// results and drops the first `offset`. Keep every other parameter the
+    /// same as the earlier query so the skipped results are the ones that
+    /// query returned. `k + offset` may not exceed
+    /// [`MAX_TOP_SNIPPETS_K`](crate::MAX_TOP_SNIPPETS_K); larger values fail with
+    /// [`Error::InvalidParameter`](crate::Error::InvalidParameter) before
+    /// anything is sent.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
 }
 
 /// Document for reranking
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RerankDocument {
     pub id: String,
     pub text: String,
 }
 
+impl RerankDocument {
+    /// A document identified by `id` in the rerank results
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+        }
+    }
+}
+
+impl From<(&str, &str)> for RerankDocument {
+    /// Convert an `(id, text)` pair
+    fn from((id, text): (&str, &str)) -> Self {
+        Self::new(id, text)
+    }
+}
+
+impl From<(String, String)> for RerankDocument {
+    /// Convert an `(id, text)` pair
+    fn from((id, text): (String, String)) -> Self {
+        Self::new(id, text)
+    }
+}
+
+/// Parameters of a rerank call
+///
+/// # Example
+/// ```
+/// use zeroentropy_community::RerankParams;
+///
+/// let params = RerankParams::new(
+///     "systems programming",
+///     [("rust", "Rust is a systems language"), ("py", "Python is a scripting language")],
+/// )
+/// .top_k(1);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct RerankParams {
+    pub(crate) query: String,
+    pub(crate) documents: Vec<RerankDocument>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) model_id: Option<Reranker>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) top_k: Option<u32>,
+    #[serde(skip)]
+    pub(crate) chunk_size: Option<usize>,
+}
+
+impl RerankParams {
+    /// Rank `documents` by relevance to `query`
+    pub fn new<I, D>(query: impl Into<String>, documents: I) -> Self
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<RerankDocument>,
+    {
+        Self {
+            query: query.into(),
+            documents: documents.into_iter().map(Into::into).collect(),
+            model_id: None,
+            top_k: None,
+            chunk_size: None,
+        }
+    }
+
+    /// Add a document to rank
+    pub fn document(mut self, document: impl Into<RerankDocument>) -> Self {
+        self.documents.push(document.into());
+        self
+    }
+
+    /// Rank with the given model instead of the default
+    pub fn model(mut self, model: impl Into<Reranker>) -> Self {
+        self.model_id = Some(model.into());
+        self
+    }
+
+    /// Only return the `top_k` best ranked documents
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
+    /// Send at most `chunk_size` documents per request, instead of
+    /// [`DEFAULT_RERANK_CHUNK_SIZE`](crate::DEFAULT_RERANK_CHUNK_SIZE)
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size.max(1));
+        self
+    }
+}
+
 /// Rerank result
 #[derive(Debug, Deserialize)]
 pub struct RerankResult {
@@ -179,3 +970,70 @@ pub struct RerankResult {
 pub struct RerankResponse {
     pub results: Vec<RerankResult>,
 }
+
+/// A model offered by the API
+#[derive(Debug, Deserialize)]
+pub struct ModelInfo {
+    /// ID to pass as `model_id` to rerank or as a query's `reranker`
+    pub model_id: String,
+}
+
+/// Response from get model list
+#[derive(Debug, Deserialize)]
+pub struct ModelListResponse {
+    pub models: Vec<ModelInfo>,
+}
+
+/// Response from parse document
+#[derive(Debug, Deserialize)]
+pub struct ParseDocumentResponse {
+    /// Text of each page, in order
+    pub pages: Vec<String>,
+}
+
+impl ParseDocumentResponse {
+    /// Text of all pages, separated by blank lines
+    pub fn text(&self) -> String {
+        self.pages.join("\n\n")
+    }
+}
+
+/// An API key of the organization, without its secret
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyInfo {
+    pub api_key_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// Response from create API key
+///
+/// Debug output masks the key.
+#[derive(Deserialize)]
+pub struct CreateApiKeyResponse {
+    pub api_key_id: String,
+    /// The new key, shown only once
+    pub api_key: String,
+}
+
+impl std::fmt::Debug for CreateApiKeyResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CreateApiKeyResponse")
+            .field("api_key_id", &self.api_key_id)
+            .field("api_key", &crate::client::redact_api_key(&self.api_key))
+            .finish()
+    }
+}
+
+/// Response from get API key list
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyListResponse {
+    pub api_keys: Vec<ApiKeyInfo>,
+}
+
+/// Response from API key revoke
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyResponse {
+    pub message: String,
+}
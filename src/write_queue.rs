@@ -0,0 +1,149 @@
+//! Write-behind queue for document writes made while offline
+//!
+//! When enabled with [`ClientBuilder::write_queue`], document add, update
+//! and delete calls that cannot reach the API are appended to a JSON Lines
+//! file instead of being lost, and fail with [`Error::WriteQueued`]. Later
+//! writes queue up behind them until [`Client::flush_write_queue`] replays
+//! the backlog in order. Each write keeps its idempotency key, so a replay
+//! of a write that did reach the server is discarded as a duplicate.
+//!
+//! [`ClientBuilder::write_queue`]: crate::ClientBuilder::write_queue
+//! [`Client::flush_write_queue`]: crate::Client::flush_write_queue
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{Mutex, MutexGuard};
+
+/// A document write waiting to be replayed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedWrite {
+    /// Endpoint path, e.g. `/documents/add-document`
+    pub endpoint: String,
+    /// JSON request body
+    pub body: serde_json::Value,
+    /// Idempotency key of the original call
+    pub idempotency_key: String,
+}
+
+/// How replayed writes rejected with a conflict (409) are handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Drop the write and report it in [`FlushReport::conflicts`]
+    #[default]
+    Skip,
+    /// Resend document adds with `overwrite` set, so the queued content
+    /// wins; other conflicting writes are skipped
+    Overwrite,
+}
+
+/// Outcome of replaying the write queue
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    /// Writes the API accepted
+    pub replayed: usize,
+    /// Writes dropped after a conflict
+    pub conflicts: Vec<QueuedWrite>,
+    /// Writes dropped because the API rejected them
+    pub rejected: Vec<(QueuedWrite, Error)>,
+    /// Writes still queued because the API could not be reached
+    pub remaining: usize,
+}
+
+/// File-backed queue shared by all clones of a client
+pub(crate) struct WriteQueue {
+    path: PathBuf,
+    conflicts: ConflictStrategy,
+    /// Number of queued writes, to skip locking when there are none
+    pending: AtomicUsize,
+    /// Serializes file access and keeps replays in order
+    lock: Mutex<()>,
+}
+
+impl WriteQueue {
+    /// Open the queue at `path`, picking up writes left by earlier runs
+    pub(crate) fn open(path: PathBuf, conflicts: ConflictStrategy) -> Result<Self> {
+        let queue = Self {
+            path,
+            conflicts,
+            pending: AtomicUsize::new(0),
+            lock: Mutex::new(()),
+        };
+        let pending = queue.load()?.len();
+        queue.pending.store(pending, Ordering::SeqCst);
+        Ok(queue)
+    }
+
+    pub(crate) fn conflicts(&self) -> ConflictStrategy {
+        self.conflicts
+    }
+
+    /// Whether writes are waiting to be replayed
+    pub(crate) fn has_pending(&self) -> bool {
+        self.pending.load(Ordering::SeqCst) > 0
+    }
+
+    pub(crate) async fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    /// Read the queued writes in order
+    pub(crate) fn load(&self) -> Result<Vec<QueuedWrite>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut writes = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                writes.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(writes)
+    }
+
+    /// Append a write to the end of the queue
+    pub(crate) fn push(&self, write: &QueuedWrite) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_vec(write)?;
+        line.push(b'\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(&line)?;
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Replace the queue contents with `writes`
+    pub(crate) fn store(&self, writes: &[QueuedWrite]) -> Result<()> {
+        if writes.is_empty() {
+            match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        } else {
+            // Write a sibling file and rename it over the queue so a crash
+            // never leaves a truncated queue behind
+            let mut contents = Vec::new();
+            for write in writes {
+                serde_json::to_writer(&mut contents, write)?;
+                contents.push(b'\n');
+            }
+            let mut temp = self.path.clone().into_os_string();
+            temp.push(".tmp");
+            fs::write(&temp, contents)?;
+            fs::rename(&temp, &self.path)?;
+        }
+        self.pending.store(writes.len(), Ordering::SeqCst);
+        Ok(())
+    }
+}
@@ -45,6 +45,21 @@ fn test_document_content_auto() {
     assert!(json.contains("\"base64_data\":\"dGVzdA==\""));
 }
 
+#[test]
+fn test_document_content_text_pages() {
+    let content = DocumentContent::TextPages {
+        pages: vec!["one".to_string(), "two".to_string()],
+    };
+
+    let json = serde_json::to_value(&content).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"type": "text-pages", "pages": ["one", "two"]})
+    );
+    let parsed: DocumentContent = serde_json::from_value(json).unwrap();
+    assert!(matches!(parsed, DocumentContent::TextPages { pages } if pages.len() == 2));
+}
+
 #[test]
 fn test_metadata_value_string() {
     let value = MetadataValue::String("test".to_string());
@@ -80,10 +95,10 @@ fn test_metadata_serialization() {
 fn test_error_display() {
     use zeroentropy_community::Error;
     
-    let err = Error::NotFound("Collection not found".to_string());
+    let err = Error::from_status(404, "Collection not found".to_string());
     assert_eq!(err.to_string(), "Not found: Collection not found");
     
-    let err = Error::Conflict("Resource already exists".to_string());
+    let err = Error::from_status(409, "Resource already exists".to_string());
     assert_eq!(err.to_string(), "Conflict: Resource already exists");
 }
 
@@ -111,3 +126,3544 @@ fn test_index_status_serialization() {
 
 // Note: Integration tests that require actual API calls should be run separately
 // with a valid API key and can be placed in a separate test file that's ignored by default
+
+#[test]
+fn test_typed_metadata_deserialization() {
+    use serde::Deserialize;
+    use zeroentropy_community::{DocumentInfoResponse, TopSnippetsResponse};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Paper {
+        title: String,
+        year: u32,
+    }
+
+    let json = r#"{
+        "document": {
+            "path": "paper.pdf",
+            "index_status": "indexed",
+            "metadata": {"title": "Attention", "year": 2017}
+        }
+    }"#;
+    let info: DocumentInfoResponse<Paper> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        info.document.metadata,
+        Some(Paper { title: "Attention".to_string(), year: 2017 })
+    );
+
+    let json = r#"{
+        "results": [
+            {"path": "paper.pdf", "content": "...", "score": 0.5, "page_number": 1, "metadata": null}
+        ]
+    }"#;
+    let results: TopSnippetsResponse<Paper> = serde_json::from_str(json).unwrap();
+    assert!(results.results[0].metadata.is_none());
+}
+
+#[test]
+fn test_request_options_timeout() {
+    use std::time::Duration;
+    use zeroentropy_community::RequestOptions;
+
+    let client = Client::new("test-key").unwrap();
+    let options = RequestOptions::new().timeout(Duration::from_secs(5));
+
+    // Resource handles accept per-request overrides without touching the client
+    let _queries = client.queries().with_options(options);
+    let _documents = client.documents().with_timeout(Duration::from_secs(600));
+}
+
+#[test]
+fn test_client_builder_with_http_client() {
+    let http_client = reqwest::Client::builder()
+        .user_agent("my-app/1.0")
+        .build()
+        .unwrap();
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .http_client(http_client)
+        .build();
+
+    assert!(client.is_ok());
+}
+
+#[test]
+fn test_client_builder_default_headers() {
+    use zeroentropy_community::Error;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .default_header("X-Routing-Key", "tenant-a")
+        .default_header("baggage", "team=search")
+        .build();
+    assert!(client.is_ok());
+
+    let result = Client::builder()
+        .api_key("test-key")
+        .default_header("Bad Header", "value")
+        .build();
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[test]
+fn test_request_options_headers_and_query() {
+    use zeroentropy_community::RequestOptions;
+
+    let client = Client::new("test-key").unwrap();
+    let options = RequestOptions::new()
+        .header("X-Experiment", "reranker-b")
+        .query("region", "eu");
+
+    let _queries = client.queries().with_options(options);
+}
+
+#[test]
+fn test_client_builder_proxy() {
+    let proxy = reqwest::Proxy::https("http://proxy.internal:3128")
+        .unwrap()
+        .basic_auth("user", "password");
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .proxy(proxy)
+        .build();
+    assert!(client.is_ok());
+
+    let client = Client::builder().api_key("test-key").no_proxy().build();
+    assert!(client.is_ok());
+}
+
+#[test]
+fn test_client_builder_tls_options() {
+    let client = Client::builder()
+        .api_key("test-key")
+        .tls_built_in_root_certs(false)
+        .build();
+    assert!(client.is_ok());
+}
+
+#[cfg(feature = "danger-insecure-tls")]
+#[test]
+fn test_client_builder_insecure_tls() {
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url("https://localhost:8443/v1")
+        .danger_accept_invalid_certs(true)
+        .build();
+    assert!(client.is_ok());
+}
+
+#[test]
+fn test_client_builder_http_version() {
+    let client = Client::builder().api_key("test-key").http1_only().build();
+    assert!(client.is_ok());
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .http2_prior_knowledge()
+        .build();
+    assert!(client.is_ok());
+}
+
+#[test]
+fn test_client_builder_connection_pool() {
+    use std::time::Duration;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .tcp_keepalive(Duration::from_secs(30))
+        .pool_idle_timeout(Some(Duration::from_secs(50)))
+        .pool_max_idle_per_host(4)
+        .build();
+    assert!(client.is_ok());
+}
+
+#[tokio::test]
+async fn test_client_builder_resolve_override() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // Minimal HTTP stub answering a single request
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0; 4096];
+        let n = socket.read(&mut request).await.unwrap();
+        let body = r#"{"collections": ["stub"]}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request[..n]).to_string()
+    });
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url(format!("http://api.stub.invalid:{}/v1", addr.port()))
+        .resolve("api.stub.invalid", addr)
+        .build()
+        .unwrap();
+
+    let response = client.collections().get_list().await.unwrap();
+    assert_eq!(response.collections, ["stub"]);
+
+    let request = server.await.unwrap();
+    assert!(request.to_lowercase().contains(&format!("host: api.stub.invalid:{}", addr.port())));
+}
+
+#[test]
+fn test_client_builder_request_compression() {
+    use zeroentropy_community::RequestCompression;
+
+    assert_eq!(RequestCompression::default(), RequestCompression::None);
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .request_compression(RequestCompression::Gzip)
+        .build();
+    assert!(client.is_ok());
+}
+
+#[test]
+fn test_client_builder_response_compression() {
+    use zeroentropy_community::CompressionStats;
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .response_compression(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(client.compression_stats(), CompressionStats::default());
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_client_creation() {
+    use std::time::Duration;
+    use zeroentropy_community::blocking;
+
+    let client = blocking::Client::new("test-api-key").unwrap();
+    let _documents = client.documents().with_timeout(Duration::from_secs(30));
+
+    let client = blocking::Client::builder()
+        .api_key("test-key")
+        .max_retries(0)
+        .build_blocking();
+    assert!(client.is_ok());
+}
+
+/// Transport double that replays canned responses and records requests
+#[derive(Clone, Default)]
+struct MockTransport {
+    responses: std::sync::Arc<std::sync::Mutex<std::collections::VecDeque<zeroentropy_community::HttpResponse>>>,
+    requests: std::sync::Arc<std::sync::Mutex<Vec<zeroentropy_community::HttpRequest>>>,
+}
+
+impl MockTransport {
+    fn with_responses(responses: Vec<(u16, &str)>) -> Self {
+        let mock = Self::default();
+        for (status, body) in responses {
+            mock.push(status, reqwest::header::HeaderMap::new(), body.as_bytes().to_vec());
+        }
+        mock
+    }
+
+    fn push(&self, status: u16, headers: reqwest::header::HeaderMap, body: Vec<u8>) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(zeroentropy_community::HttpResponse { status, headers, body });
+    }
+
+    fn requests(&self) -> Vec<zeroentropy_community::HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[zeroentropy_community::async_trait]
+impl zeroentropy_community::HttpTransport for MockTransport {
+    async fn send(
+        &self,
+        request: zeroentropy_community::HttpRequest,
+    ) -> zeroentropy_community::Result<zeroentropy_community::HttpResponse> {
+        self.requests.lock().unwrap().push(request);
+        Ok(self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("no canned response left"))
+    }
+}
+
+#[tokio::test]
+async fn test_custom_transport_retries_and_headers() {
+    use zeroentropy_community::RequestOptions;
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": ["a", "b"]}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url("https://api.test/v1")
+        .default_header("X-Tenant", "acme")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let response = client
+        .collections()
+        .with_options(RequestOptions::new().header("X-Experiment", "b").query("region", "eu"))
+        .get_list()
+        .await
+        .unwrap();
+    assert_eq!(response.collections, vec!["a", "b"]);
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    let request = &requests[0];
+    assert_eq!(
+        request.url,
+        "https://api.test/v1/collections/get-collection-list?region=eu"
+    );
+    assert_eq!(request.headers["authorization"], "Bearer test-key");
+    assert_eq!(request.headers["x-tenant"], "acme");
+    assert_eq!(request.headers["x-experiment"], "b");
+}
+
+#[tokio::test]
+async fn test_error_status_mapping() {
+    use zeroentropy_community::Error;
+
+    let mock = MockTransport::with_responses(vec![(404, r#"{"message": "no such collection"}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock)
+        .build()
+        .unwrap();
+
+    let err = client.collections().delete("missing").await.unwrap_err();
+    assert!(matches!(err, Error::NotFound(ref e) if e.message == "no such collection"));
+}
+
+#[tokio::test]
+async fn test_request_and_response_compression() {
+    use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+    use std::io::{Read, Write};
+    use zeroentropy_community::RequestCompression;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(br#"{"message": "ok"}"#).unwrap();
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("content-encoding", "gzip".parse().unwrap());
+
+    let mock = MockTransport::default();
+    mock.push(200, headers, encoder.finish().unwrap());
+    let client = Client::builder()
+        .api_key("test-key")
+        .request_compression(RequestCompression::Gzip)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let text = "lorem ipsum ".repeat(200);
+    let response = client
+        .documents()
+        .add_text("collection", "doc.txt", text.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(response.message, "ok");
+
+    let request = &mock.requests()[0];
+    assert_eq!(request.headers["content-encoding"], "gzip");
+    let mut json = String::new();
+    GzDecoder::new(&request.body[..]).read_to_string(&mut json).unwrap();
+    assert!(json.contains(&text));
+
+    let stats = client.compression_stats();
+    assert_eq!(stats.responses, 1);
+    assert_eq!(stats.compressed_responses, 1);
+}
+
+#[tokio::test]
+async fn test_interceptor_hooks() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use zeroentropy_community::{HttpRequest, HttpResponse, Interceptor, RetryEvent};
+
+    #[derive(Default)]
+    struct Counter {
+        responses: AtomicU32,
+        retries: AtomicU32,
+    }
+
+    struct Recorder(Arc<Counter>);
+
+    impl Interceptor for Recorder {
+        fn on_request(&self, request: &mut HttpRequest) {
+            request.headers.insert("x-trace-id", "trace-1".parse().unwrap());
+        }
+
+        fn on_response(&self, _request: &HttpRequest, _response: &HttpResponse) {
+            self.0.responses.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_retry(&self, event: &RetryEvent<'_>) {
+            assert_eq!(event.status, Some(429));
+            self.0.retries.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let counter = Arc::new(Counter::default());
+    let mock = MockTransport::with_responses(vec![
+        (429, r#"{"message": "slow down"}"#),
+        (200, r#"{"message": "created"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .interceptor(Recorder(counter.clone()))
+        .build()
+        .unwrap();
+
+    client.collections().add("new").await.unwrap();
+
+    assert_eq!(counter.responses.load(Ordering::SeqCst), 2);
+    assert_eq!(counter.retries.load(Ordering::SeqCst), 1);
+    assert!(mock
+        .requests()
+        .iter()
+        .all(|request| request.headers["x-trace-id"] == "trace-1"));
+}
+
+#[cfg(feature = "middleware")]
+#[test]
+fn test_client_builder_middleware_client() {
+    let middleware_client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .middleware_client(middleware_client)
+        .build();
+    assert!(client.is_ok());
+}
+
+#[cfg(feature = "tower")]
+#[tokio::test]
+async fn test_tower_service_call() {
+    use tower_service::Service;
+    use zeroentropy_community::ZeroEntropyRequest;
+
+    let mock = MockTransport::with_responses(vec![(200, r#"{"collections": ["a"]}"#)]);
+    let mut client = Client::builder()
+        .api_key("test-key")
+        .base_url("https://api.test/v1")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let response = client
+        .call(ZeroEntropyRequest::new(
+            "/collections/get-collection-list",
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response["collections"][0], "a");
+    assert_eq!(
+        mock.requests()[0].url,
+        "https://api.test/v1/collections/get-collection-list"
+    );
+}
+
+#[tokio::test]
+async fn test_request_id_attached_to_requests_and_errors() {
+    use zeroentropy_community::RequestOptions;
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"collections": []}"#),
+        (400, r#"{"message": "bad collection name"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+    let generated = mock.requests()[0].headers["x-request-id"].to_str().unwrap().to_string();
+    assert_eq!(generated.len(), 36);
+
+    let err = client
+        .collections()
+        .with_options(RequestOptions::new().request_id("job-42"))
+        .add("bad name")
+        .await
+        .unwrap_err();
+    assert_eq!(mock.requests()[1].headers["x-request-id"], "job-42");
+    assert_eq!(err.request_id(), Some("job-42"));
+    assert_eq!(err.api_error().unwrap().status, 400);
+}
+
+#[tokio::test]
+async fn test_idempotency_key_on_document_writes() {
+    use zeroentropy_community::RequestOptions;
+
+    let mock = MockTransport::with_responses(vec![
+        (500, r#"{"message": "oops"}"#),
+        (200, r#"{"message": "ok"}"#),
+        (200, r#"{"message": "ok"}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_writes(true)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.documents().delete("c", "doc.txt").await.unwrap();
+    client
+        .documents()
+        .with_options(RequestOptions::new().idempotency_key("upload-7"))
+        .add_text("c", "doc.txt", "hello", None)
+        .await
+        .unwrap();
+    client.collections().get_list().await.unwrap();
+
+    let requests = mock.requests();
+    // A retried write reuses its key
+    assert_eq!(
+        requests[0].headers["idempotency-key"],
+        requests[1].headers["idempotency-key"]
+    );
+    assert_eq!(requests[2].headers["idempotency-key"], "upload-7");
+    assert!(!requests[3].headers.contains_key("idempotency-key"));
+}
+
+#[tokio::test]
+async fn test_api_key_rotation() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use zeroentropy_community::{async_trait, ApiKeyProvider};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"collections": []}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("old-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+    client.clone().set_api_key("new-key");
+    client.collections().get_list().await.unwrap();
+
+    let requests = mock.requests();
+    assert_eq!(requests[0].headers["authorization"], "Bearer old-key");
+    assert_eq!(requests[1].headers["authorization"], "Bearer new-key");
+
+    struct Counting(AtomicU32);
+
+    #[async_trait]
+    impl ApiKeyProvider for Counting {
+        async fn api_key(&self) -> zeroentropy_community::Result<String> {
+            Ok(format!("key-{}", self.0.fetch_add(1, Ordering::SeqCst)))
+        }
+    }
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key_provider(Counting(AtomicU32::new(0)))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+
+    // The key is resolved again for every attempt
+    let requests = mock.requests();
+    assert_eq!(requests[0].headers["authorization"], "Bearer key-0");
+    assert_eq!(requests[1].headers["authorization"], "Bearer key-1");
+}
+
+#[test]
+fn test_profiles() {
+    use zeroentropy_community::Error;
+
+    let path = std::env::temp_dir().join(format!("zeroentropy-profiles-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"
+[default]
+api_key = "dev-key"
+
+[prod]
+api_key = "prod-key"
+base_url = "https://example.com/v1"
+timeout_secs = 120
+max_retries = 5
+project = "production"
+"#,
+    )
+    .unwrap();
+
+    let client = Client::from_profile_file(&path, "prod").unwrap();
+    assert_eq!(client.project(), Some("production"));
+    assert!(Client::builder().profile_file(&path, "default").unwrap().build().is_ok());
+
+    let result = Client::from_profile_file(&path, "staging");
+    assert!(matches!(result, Err(Error::InvalidConfig(msg)) if msg.contains("staging")));
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(Client::from_profile_file(&path, "prod"), Err(Error::Io(_))));
+}
+
+#[tokio::test]
+async fn test_base_url_failover() {
+    use std::time::Duration;
+
+    let mock = MockTransport::with_responses(vec![
+        (502, r#"{"message": "bad gateway"}"#),
+        (200, r#"{"collections": []}"#),
+        (404, r#"{"message": "not found"}"#),
+    ]);
+    let retries = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = retries.clone();
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_urls(["https://mirror.test/v1", "https://api.test/v1"])
+        .max_retries(0)
+        .on_retry(move |event| {
+            recorded.lock().unwrap().push((event.attempt, event.delay, event.status))
+        })
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+    // Client errors are not failed over
+    assert!(client.collections().get_list().await.is_err());
+
+    // The failover is reported as an immediate retry
+    assert_eq!(*retries.lock().unwrap(), [(1, Duration::ZERO, Some(502))]);
+    assert_eq!(client.stats().retries, 1);
+
+    let urls: Vec<_> = mock.requests().into_iter().map(|r| r.url).collect();
+    assert_eq!(
+        urls,
+        [
+            "https://mirror.test/v1/collections/get-collection-list",
+            "https://api.test/v1/collections/get-collection-list",
+            "https://mirror.test/v1/collections/get-collection-list",
+        ]
+    );
+
+    // An open circuit on the first URL does not block the second one
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": []}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_urls(["https://mirror.test/v1", "https://api.test/v1"])
+        .circuit_breaker(1, Duration::from_secs(60))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+    client.collections().get_list().await.unwrap();
+    let urls: Vec<_> = mock.requests().into_iter().map(|r| r.url).collect();
+    assert_eq!(
+        urls,
+        [
+            "https://mirror.test/v1/collections/get-collection-list",
+            "https://api.test/v1/collections/get-collection-list",
+            "https://api.test/v1/collections/get-collection-list",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_failover_does_not_replay_timed_out_writes() {
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    // Stub that reads requests and never answers them
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let log = received.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let log = log.clone();
+            tokio::spawn(async move {
+                let mut request = vec![0; 4096];
+                let n = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..n]).to_string();
+                log.lock().unwrap().push(request.lines().next().unwrap_or("").to_string());
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            });
+        }
+    });
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_urls([
+            format!("http://127.0.0.1:{}/primary", port),
+            format!("http://127.0.0.1:{}/mirror", port),
+        ])
+        .timeout(Duration::from_millis(200))
+        .max_retries(0)
+        .build()
+        .unwrap();
+
+    let err = client
+        .documents()
+        .add_text("docs", "a.txt", "text", None)
+        .await
+        .unwrap_err();
+    assert!(err.is_retryable());
+    assert_eq!(
+        *received.lock().unwrap(),
+        ["POST /primary/documents/add-document HTTP/1.1"]
+    );
+}
+
+#[tokio::test]
+async fn test_api_version() {
+    use zeroentropy_community::DEFAULT_API_VERSION;
+
+    assert_eq!(DEFAULT_API_VERSION, "v1");
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"collections": []}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .api_version("v2")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    client.collections().get_list().await.unwrap();
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .base_url("https://gateway.test/")
+        .api_version("2025-01-preview")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    client.collections().get_list().await.unwrap();
+
+    let urls: Vec<_> = mock.requests().into_iter().map(|r| r.url).collect();
+    assert_eq!(
+        urls,
+        [
+            "https://api.zeroentropy.dev/v2/collections/get-collection-list",
+            "https://gateway.test/2025-01-preview/collections/get-collection-list",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use zeroentropy_community::{async_trait, HttpRequest, HttpResponse, HttpTransport};
+
+    #[derive(Clone, Default)]
+    struct SlowTransport {
+        in_flight: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl HttpTransport for SlowTransport {
+        async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(HttpResponse {
+                status: 200,
+                headers: Default::default(),
+                body: br#"{"collections": []}"#.to_vec(),
+            })
+        }
+    }
+
+    let transport = SlowTransport::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .max_concurrent_requests(2)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.collections().get_list().await })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap().unwrap();
+    }
+    assert_eq!(transport.peak.load(Ordering::SeqCst), 2);
+
+    let result = Client::builder().api_key("test-key").max_concurrent_requests(0).build();
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let mock = MockTransport::with_responses(vec![(200, r#"{"collections": []}"#); 4]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .rate_limit(20.0, 2)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    // Two requests fit in the burst, the next two wait 50ms each
+    let start = std::time::Instant::now();
+    for _ in 0..4 {
+        client.collections().get_list().await.unwrap();
+    }
+    let elapsed = start.elapsed();
+    assert!(elapsed >= std::time::Duration::from_millis(90), "{:?}", elapsed);
+    assert_eq!(mock.requests().len(), 4);
+
+    assert!(Client::builder().api_key("test-key").rate_limit(0.0, 1).build().is_err());
+    assert!(Client::builder().api_key("test-key").rate_limit(5.0, 0).build().is_err());
+}
+
+#[tokio::test]
+async fn test_coalesce_identical_queries() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use zeroentropy_community::{async_trait, HttpRequest, HttpResponse, HttpTransport};
+
+    #[derive(Clone, Default)]
+    struct CountingTransport {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl HttpTransport for CountingTransport {
+        async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Ok(HttpResponse {
+                status: 200,
+                headers: Default::default(),
+                body: br#"{"results": [{"path": "a.txt", "content": "hit", "score": 0.9}]}"#.to_vec(),
+            })
+        }
+    }
+
+    let transport = CountingTransport::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .coalesce_identical_queries(true)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let search = |query: &'static str| {
+        let client = client.clone();
+        tokio::spawn(async move {
+            client
+                .queries()
+                .top_snippets("docs", query, 5, None, None, None, None)
+                .await
+        })
+    };
+    let handles = vec![search("rust"), search("rust"), search("rust"), search("python")];
+    for handle in handles {
+        let response = handle.await.unwrap().unwrap();
+        assert_eq!(response.results[0].content, "hit");
+    }
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 2);
+
+    // Completed calls are not cached
+    search("rust").await.unwrap().unwrap();
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 3);
+
+    // Per-caller deadlines do not keep identical queries apart
+    let with_deadline = || {
+        let client = client.clone();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let options = zeroentropy_community::RequestOptions::new().deadline(deadline);
+        tokio::spawn(async move {
+            client
+                .queries()
+                .with_options(options)
+                .top_snippets("docs", "deadline", 5, None, None, None, None)
+                .await
+        })
+    };
+    let first = with_deadline();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let second = with_deadline();
+    first.await.unwrap().unwrap();
+    second.await.unwrap().unwrap();
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 4);
+
+    // Callers sharing a cancelled call see the cancellation
+    let token = zeroentropy_community::CancellationToken::new();
+    let cancellable = || {
+        let client = client.clone();
+        let options = zeroentropy_community::RequestOptions::new().cancellation_token(token.clone());
+        tokio::spawn(async move {
+            client
+                .queries()
+                .with_options(options)
+                .top_snippets("docs", "cancelled", 5, None, None, None, None)
+                .await
+        })
+    };
+    let handles = vec![cancellable(), cancellable()];
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    token.cancel();
+    for handle in handles {
+        let err = handle.await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), zeroentropy_community::ErrorKind::Cancelled);
+        assert!(!err.is_retryable());
+    }
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn test_response_cache() {
+    use std::time::Duration;
+    use zeroentropy_community::InMemoryCache;
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+        (200, r#"{"message": "created"}"#),
+        (200, r#"{"message": "created"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .cache(InMemoryCache::new(10), Duration::from_secs(60))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let queries = client.queries();
+    queries.top_documents("docs", "rust", 5, None, None, None, None).await.unwrap();
+    queries.top_documents("docs", "rust", 5, None, None, None, None).await.unwrap();
+    assert_eq!(mock.requests().len(), 1);
+
+    queries.top_documents("docs", "python", 5, None, None, None, None).await.unwrap();
+    assert_eq!(mock.requests().len(), 2);
+
+    // Writes always reach the API
+    client.collections().add("docs").await.unwrap();
+    client.collections().add("docs").await.unwrap();
+    assert_eq!(mock.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_get_content_to_writer() {
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"document": {"path": "a.txt", "metadata": {"content": "not this"}, "content": "line 1\nquote \" café 😀", "index_status": "indexed"}}"#,
+        ),
+        (200, r#"{"document": {"path": "a.txt", "content": null}}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let mut output = Vec::new();
+    let written = client
+        .documents()
+        .get_content_to_writer("docs", "a.txt", &mut output)
+        .await
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "line 1\nquote \" café 😀");
+    assert_eq!(written, "line 1\nquote \" café 😀".len() as u64);
+    assert_eq!(mock.requests()[0].headers["accept-encoding"], "identity");
+
+    let mut output = Vec::new();
+    let result = client
+        .documents()
+        .get_content_to_writer("docs", "a.txt", &mut output)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_hedged_queries() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use zeroentropy_community::{async_trait, HttpRequest, HttpResponse, HttpTransport};
+
+    // The first request stalls; later ones answer immediately
+    #[derive(Clone, Default)]
+    struct StallFirst {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl HttpTransport for StallFirst {
+        async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let path = if call == 0 {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "slow.txt"
+            } else {
+                "fast.txt"
+            };
+            Ok(HttpResponse {
+                status: 200,
+                headers: Default::default(),
+                body: format!(r#"{{"results": [{{"path": "{}", "score": 0.5}}]}}"#, path).into_bytes(),
+            })
+        }
+    }
+
+    let transport = StallFirst::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .hedge_queries(Duration::from_millis(20))
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let response = client
+        .queries()
+        .top_documents("docs", "rust", 5, None, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.results[0].path, "fast.txt");
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 2);
+
+    // Fast responses never trigger the hedge
+    client.queries().top_documents("docs", "rust", 5, None, None, None, None).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_cancellation_and_deadline() {
+    use std::time::{Duration, Instant};
+    use zeroentropy_community::{async_trait, CancellationToken, Error, HttpRequest, HttpResponse, HttpTransport, Jitter, RequestOptions};
+
+    struct Hang;
+
+    #[async_trait]
+    impl HttpTransport for Hang {
+        async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            unreachable!()
+        }
+    }
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(Hang)
+        .build()
+        .unwrap();
+
+    let token = CancellationToken::new();
+    let search = client
+        .queries()
+        .with_options(RequestOptions::new().cancellation_token(token.clone()));
+    let cancel = async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+    };
+    let (result, _) = tokio::join!(search.top_documents("docs", "rust", 5, None, None, None, None), cancel);
+    assert!(matches!(result, Err(Error::Cancelled)));
+
+    let result = client
+        .queries()
+        .with_options(RequestOptions::new().deadline(Instant::now() + Duration::from_millis(20)))
+        .top_documents("docs", "rust", 5, None, None, None, None)
+        .await;
+    assert!(matches!(result, Err(Error::DeadlineExceeded)));
+
+    // No retry is scheduled past the deadline
+    let mock = MockTransport::with_responses(vec![(503, r#"{"message": "unavailable"}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_jitter(Jitter::None)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let result = client
+        .collections()
+        .with_options(RequestOptions::new().deadline(Instant::now() + Duration::from_millis(100)))
+        .get_list()
+        .await;
+    assert!(matches!(result, Err(Error::InternalServerError(_))));
+    assert_eq!(mock.requests().len(), 1);
+    assert!(mock.requests()[0].timeout.unwrap() <= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown() {
+    use std::time::Duration;
+    use zeroentropy_community::{async_trait, Error, HttpRequest, HttpResponse, HttpTransport};
+
+    // Snippet queries hang, everything else answers after a short delay
+    struct Mixed;
+
+    #[async_trait]
+    impl HttpTransport for Mixed {
+        async fn send(&self, request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            let delay = if request.url.ends_with("top-snippets") { 10_000 } else { 20 };
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            Ok(HttpResponse {
+                status: 200,
+                headers: Default::default(),
+                body: br#"{"collections": []}"#.to_vec(),
+            })
+        }
+    }
+
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(Mixed)
+        .build()
+        .unwrap();
+
+    let list = {
+        let client = client.clone();
+        tokio::spawn(async move { client.collections().get_list().await })
+    };
+    let _snippets = {
+        let client = client.clone();
+        tokio::spawn(async move {
+            client.queries().top_snippets("docs", "q", 5, None, None, None, None).await
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    let report = client.shutdown(Duration::from_millis(100)).await;
+    assert_eq!(report.abandoned, ["/queries/top-snippets"]);
+    assert!(list.await.unwrap().is_ok());
+
+    let result = client.collections().get_list().await;
+    assert!(matches!(result, Err(Error::ShutDown)));
+}
+
+#[tokio::test]
+async fn test_default_collection_and_metadata() {
+    use serde_json::{json, Value};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"message": "ok"}"#),
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .default_collection("articles")
+        .default_metadata("tenant_id", "acme")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    assert_eq!(client.default_collection_name(), Some("articles"));
+
+    let collection = client.collection().unwrap();
+    let mut metadata = HashMap::new();
+    metadata.insert("lang".to_string(), MetadataValue::String("en".to_string()));
+    collection.add_text("a.txt", "hello", Some(metadata)).await.unwrap();
+    collection.top_snippets("hello", 5, None, None, None, None).await.unwrap();
+
+    // An explicit constraint on the key wins over the default
+    let filter = json!({"tenant_id": {"$eq": "other"}}).as_object().unwrap().clone();
+    client
+        .queries()
+        .top_documents("articles", "hello", 5, Some(filter.into_iter().collect()), None, None, None)
+        .await
+        .unwrap();
+
+    let bodies: Vec<Value> = mock
+        .requests()
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(bodies[0]["collection_name"], "articles");
+    assert_eq!(bodies[0]["metadata"], json!({"lang": "en", "tenant_id": "acme"}));
+    assert_eq!(bodies[1]["filter"], json!({"tenant_id": {"$eq": "acme"}}));
+    assert_eq!(bodies[2]["filter"], json!({"tenant_id": {"$eq": "other"}}));
+
+    let client = Client::builder().api_key("test-key").build().unwrap();
+    assert!(client.collection().is_err());
+}
+
+#[tokio::test]
+async fn test_retry_after_header() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use zeroentropy_community::{Interceptor, Jitter, RetryEvent};
+
+    #[derive(Clone, Default)]
+    struct Delays(Arc<Mutex<Vec<Duration>>>);
+
+    impl Interceptor for Delays {
+        fn on_retry(&self, event: &RetryEvent<'_>) {
+            self.0.lock().unwrap().push(event.delay);
+        }
+    }
+
+    let mock = MockTransport::default();
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("retry-after", "0".parse().unwrap());
+    mock.push(429, headers, br#"{"message": "slow down"}"#.to_vec());
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+    mock.push(503, headers, br#"{"message": "unavailable"}"#.to_vec());
+    mock.push(429, Default::default(), br#"{"message": "slow down"}"#.to_vec());
+    mock.push(200, Default::default(), br#"{"collections": []}"#.to_vec());
+
+    let delays = Delays::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .max_retries(3)
+        .retry_jitter(Jitter::None)
+        .interceptor(delays.clone())
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    client.collections().get_list().await.unwrap();
+
+    // Seconds, a date in the past, then backoff once the header is absent
+    assert_eq!(
+        *delays.0.lock().unwrap(),
+        [Duration::ZERO, Duration::ZERO, Duration::from_millis(2000)]
+    );
+}
+
+#[tokio::test]
+async fn test_retryable_statuses() {
+    use zeroentropy_community::Error;
+
+    // Conflicts are not retried by default
+    let mock = MockTransport::with_responses(vec![(409, r#"{"message": "already exists"}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let result = client.collections().add("docs").await;
+    assert!(matches!(result, Err(Error::Conflict(_))));
+    assert_eq!(mock.requests().len(), 1);
+
+    let mock = MockTransport::with_responses(vec![
+        (409, r#"{"message": "locked"}"#),
+        (501, r#"{"message": "not implemented"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retryable_statuses([409])
+        .retry_writes(true)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let result = client.collections().add("docs").await;
+    assert!(matches!(result, Err(Error::InternalServerError(_))));
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_policy() {
+    use std::time::Duration;
+    use zeroentropy_community::{ConstantBackoff, Error, NoRetry, RetryPolicy};
+
+    struct ConflictsOnly;
+
+    impl RetryPolicy for ConflictsOnly {
+        fn should_retry(&self, attempt: u32, error: &Error) -> Option<Duration> {
+            (attempt <= 1 && matches!(error, Error::Conflict(_))).then_some(Duration::ZERO)
+        }
+    }
+
+    let mock = MockTransport::with_responses(vec![
+        (409, r#"{"message": "locked"}"#),
+        (409, r#"{"message": "locked"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(ConflictsOnly)
+        .retry_writes(true)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let result = client.collections().add("docs").await;
+    assert!(matches!(result, Err(Error::Conflict(_))));
+    assert_eq!(mock.requests().len(), 2);
+
+    let mock = MockTransport::with_responses(vec![(503, r#"{"message": "unavailable"}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(NoRetry)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    assert!(client.collections().get_list().await.is_err());
+    assert_eq!(mock.requests().len(), 1);
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (502, r#"{"message": "bad gateway"}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(ConstantBackoff::new(2, Duration::from_millis(1)))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    client.collections().get_list().await.unwrap();
+    assert_eq!(mock.requests().len(), 3);
+}
+
+#[test]
+fn test_backoff_jitter() {
+    use std::time::Duration;
+    use zeroentropy_community::{Error, ExponentialBackoff, Jitter, RetryPolicy};
+
+    let error = Error::from_status(503, "unavailable".to_string());
+    let plain = ExponentialBackoff::new(10).jitter(Jitter::None);
+    let full = ExponentialBackoff::new(10);
+    let decorrelated = ExponentialBackoff::new(10).jitter(Jitter::Decorrelated);
+    for attempt in 1..=6 {
+        let ceiling = plain.should_retry(attempt, &error).unwrap();
+        let expected = Duration::from_millis(500 * 2_u64.pow(attempt - 1));
+        assert_eq!(ceiling, expected.min(Duration::from_secs(8)));
+        assert!(full.should_retry(attempt, &error).unwrap() <= ceiling);
+        let delay = decorrelated.should_retry(attempt, &error).unwrap();
+        assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(8));
+    }
+    assert_eq!(full.should_retry(11, &error), None);
+}
+
+#[tokio::test]
+async fn test_max_elapsed_time() {
+    use std::time::Duration;
+    use zeroentropy_community::{ConstantBackoff, Error};
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (503, r#"{"message": "unavailable"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(ConstantBackoff::new(5, Duration::from_millis(60)))
+        .max_elapsed_time(Duration::from_millis(100))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let result = client.collections().get_list().await;
+    match result {
+        Err(Error::RetryBudgetExhausted { attempts, last_error }) => {
+            assert_eq!(attempts, 2);
+            assert!(matches!(*last_error, Error::InternalServerError(_)));
+        }
+        other => panic!("expected an exhausted retry budget, got {:?}", other.map(|_| ())),
+    }
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker() {
+    use std::time::Duration;
+    use zeroentropy_community::{ConstantBackoff, Error};
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(ConstantBackoff::new(5, Duration::ZERO))
+        .circuit_breaker(2, Duration::from_millis(50))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    // The second failure opens the circuit and stops the retries
+    let result = client.collections().get_list().await;
+    assert!(matches!(result, Err(Error::CircuitOpen)));
+    assert_eq!(mock.requests().len(), 2);
+    let result = client.collections().get_list().await;
+    assert!(matches!(result, Err(Error::CircuitOpen)));
+    assert_eq!(mock.requests().len(), 2);
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    client.collections().get_list().await.unwrap();
+    assert_eq!(mock.requests().len(), 3);
+
+    let result = Client::builder()
+        .api_key("test-key")
+        .circuit_breaker(0, Duration::from_secs(1))
+        .build();
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[test]
+fn test_error_classification() {
+    use zeroentropy_community::Error;
+
+    let rate_limited = Error::from_status(429, "slow down".to_string());
+    assert!(rate_limited.is_rate_limited());
+    assert!(rate_limited.is_retryable());
+    assert_eq!(rate_limited.status_code(), Some(429));
+
+    let not_found = Error::from_status(404, "no such collection".to_string());
+    assert!(not_found.is_not_found());
+    assert!(!not_found.is_retryable());
+    assert_eq!(not_found.status_code(), Some(404));
+
+    let conflict = Error::from_status(409, "already exists".to_string());
+    assert!(!conflict.is_retryable());
+
+    let transport = Error::Transport("connection reset".into());
+    assert!(transport.is_retryable());
+    assert_eq!(transport.status_code(), None);
+    assert!(!Error::InvalidApiKey.is_retryable());
+}
+
+#[tokio::test]
+async fn test_structured_error_body() {
+    use zeroentropy_community::{Error, ErrorDetail};
+
+    let mock = MockTransport::with_responses(vec![
+        (
+            422,
+            r#"{"detail": [{"loc": ["body", "k"], "msg": "must be at least 1", "type": "greater_than_equal"}]}"#,
+        ),
+        (404, r#"{"detail": "Collection not found", "code": "collection_not_found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let error = client
+        .queries()
+        .top_documents("docs", "rust", 0, None, None, None, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::UnprocessableEntity(_)));
+    let api_error = error.api_error().unwrap();
+    assert_eq!(api_error.message, "body.k: must be at least 1");
+    let validation = api_error.validation_errors();
+    assert_eq!(validation.len(), 1);
+    assert_eq!(validation[0].kind, "greater_than_equal");
+    assert_eq!(
+        error.field_errors(),
+        [("k".to_string(), "must be at least 1".to_string())]
+    );
+
+    let error = client.collections().delete("missing").await.unwrap_err();
+    let api_error = error.api_error().unwrap();
+    assert_eq!(api_error.message, "Collection not found");
+    let body = api_error.body.as_ref().unwrap();
+    assert_eq!(body.code.as_deref(), Some("collection_not_found"));
+    assert_eq!(body.detail, Some(ErrorDetail::Message("Collection not found".to_string())));
+    assert!(api_error.validation_errors().is_empty());
+    assert!(error.field_errors().is_empty());
+}
+
+#[tokio::test]
+async fn test_error_request_context() {
+    let body = r#"{"message": "invalid query"}"#;
+    let mock = MockTransport::with_responses(vec![(400, body)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let options = zeroentropy_community::RequestOptions::new().request_id("req-42");
+
+    let error = client
+        .queries()
+        .with_options(options)
+        .top_snippets("docs", "rust", 5, None, None, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.endpoint(), Some("/queries/top-snippets"));
+    assert_eq!(error.status_code(), Some(400));
+    assert_eq!(error.request_id(), Some("req-42"));
+    assert_eq!(error.raw_body(), Some(body.as_bytes()));
+    assert_eq!(zeroentropy_community::Error::InvalidApiKey.endpoint(), None);
+}
+
+#[tokio::test]
+async fn test_post_with_meta() {
+    use zeroentropy_community::{CollectionListResponse, RequestOptions};
+
+    let mock = MockTransport::default();
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-ratelimit-limit", "100".parse().unwrap());
+    headers.insert("x-ratelimit-remaining", "42".parse().unwrap());
+    mock.push(200, headers, br#"{"collections": ["a"]}"#.to_vec());
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let response = client
+        .post_with_meta::<_, CollectionListResponse>(
+            "/collections/get-collection-list",
+            &serde_json::json!({}),
+            &RequestOptions::new().request_id("req-7"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.collections, ["a"]);
+    assert_eq!(response.request_id.as_deref(), Some("req-7"));
+    assert_eq!(response.rate_limit_limit(), Some(100));
+    assert_eq!(response.rate_limit_remaining(), Some(42));
+    assert_eq!(response.into_inner().collections, ["a"]);
+}
+
+#[tokio::test]
+async fn test_rate_limit_status() {
+    use std::time::Duration;
+
+    let mock = MockTransport::default();
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-ratelimit-limit", "100".parse().unwrap());
+    headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+    headers.insert("x-ratelimit-reset", "30".parse().unwrap());
+    mock.push(200, headers, br#"{"collections": []}"#.to_vec());
+    mock.push(200, Default::default(), br#"{"collections": []}"#.to_vec());
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    assert_eq!(client.rate_limit_status(), None);
+
+    client.collections().get_list().await.unwrap();
+    let status = client.rate_limit_status().unwrap();
+    assert_eq!(status.limit, Some(100));
+    assert_eq!(status.remaining, Some(0));
+    assert!(status.is_exhausted());
+    let reset_in = status.reset_in().unwrap();
+    assert!(reset_in > Duration::from_secs(29) && reset_in <= Duration::from_secs(30));
+
+    // Responses without the headers keep the last known state
+    client.collections().get_list().await.unwrap();
+    assert_eq!(client.rate_limit_status(), Some(status));
+}
+
+#[tokio::test]
+async fn test_on_retry_callback() {
+    use std::sync::{Arc, Mutex};
+    use zeroentropy_community::{ConstantBackoff, Error};
+
+    let mock = MockTransport::with_responses(vec![
+        (429, r#"{"message": "slow down"}"#),
+        (502, r#"{"message": "bad gateway"}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = seen.clone();
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(ConstantBackoff::new(2, std::time::Duration::ZERO))
+        .on_retry(move |event| {
+            assert!(matches!(
+                event.error,
+                Error::RateLimitExceeded(_) | Error::InternalServerError(_)
+            ));
+            recorder.lock().unwrap().push((event.attempt, event.status));
+        })
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+    assert_eq!(*seen.lock().unwrap(), [(1, Some(429)), (2, Some(502))]);
+}
+
+#[tokio::test]
+async fn test_write_queue() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use zeroentropy_community::{
+        async_trait, ConflictStrategy, Error, HttpRequest, HttpResponse, HttpTransport,
+    };
+
+    /// Fails like an unreachable server while `offline` is set
+    #[derive(Clone)]
+    struct Flaky {
+        offline: Arc<AtomicBool>,
+        inner: MockTransport,
+    }
+
+    #[async_trait]
+    impl HttpTransport for Flaky {
+        async fn send(&self, request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            if self.offline.load(Ordering::SeqCst) {
+                let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+                return Err(Error::Transport(Box::new(refused)));
+            }
+            self.inner.send(request).await
+        }
+    }
+
+    let path = std::env::temp_dir().join(format!("ze-write-queue-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let transport = Flaky {
+        offline: Arc::new(AtomicBool::new(true)),
+        inner: MockTransport::with_responses(vec![
+            (200, r#"{"message": "added"}"#),
+            (409, r#"{"message": "already exists"}"#),
+            (200, r#"{"message": "overwritten"}"#),
+        ]),
+    };
+    let client = Client::builder()
+        .api_key("test-key")
+        .max_retries(0)
+        .write_queue(&path)
+        .write_queue_conflicts(ConflictStrategy::Overwrite)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let result = client.documents().add_text("docs", "a.txt", "first", None).await;
+    assert!(matches!(result, Err(Error::WriteQueued)));
+    transport.offline.store(false, Ordering::SeqCst);
+    // Queued behind the first write even though the API is reachable again
+    let result = client.documents().add_text("docs", "b.txt", "second", None).await;
+    assert!(matches!(result, Err(Error::WriteQueued)));
+    assert!(transport.inner.requests().is_empty());
+
+    // The queue survives a restart
+    let client = Client::builder()
+        .api_key("test-key")
+        .write_queue(&path)
+        .write_queue_conflicts(ConflictStrategy::Overwrite)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    let report = client.flush_write_queue().await.unwrap();
+    assert_eq!(report.replayed, 2);
+    assert!(report.conflicts.is_empty() && report.rejected.is_empty());
+    assert_eq!(report.remaining, 0);
+    assert!(!path.exists());
+
+    let requests = transport.inner.requests();
+    let bodies: Vec<serde_json::Value> = requests
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(bodies[0]["path"], "a.txt");
+    assert_eq!(bodies[1]["path"], "b.txt");
+    assert_eq!(bodies[2]["overwrite"], true);
+    assert!(requests.iter().all(|r| r.headers.contains_key("idempotency-key")));
+}
+
+#[tokio::test]
+async fn test_write_queue_skips_timed_out_writes() {
+    use zeroentropy_community::{async_trait, Error, HttpRequest, HttpResponse, HttpTransport};
+
+    /// Times out after the request may have reached the API
+    struct TimingOut;
+
+    #[async_trait]
+    impl HttpTransport for TimingOut {
+        async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            let timeout = std::io::Error::from(std::io::ErrorKind::TimedOut);
+            Err(Error::Transport(Box::new(timeout)))
+        }
+    }
+
+    let path = std::env::temp_dir().join(format!("ze-write-timeout-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = Client::builder()
+        .api_key("test-key")
+        .max_retries(0)
+        .write_queue(&path)
+        .transport(TimingOut)
+        .build()
+        .unwrap();
+
+    let err = client
+        .documents()
+        .add_text("docs", "a.txt", "text", None)
+        .await
+        .unwrap_err();
+    assert!(!matches!(err, Error::WriteQueued));
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_error_kind() {
+    use zeroentropy_community::{Error, ErrorKind};
+
+    assert_eq!(Error::from_status(422, "invalid".to_string()).kind(), ErrorKind::InvalidRequest);
+    assert_eq!(Error::from_status(429, "slow down".to_string()).kind(), ErrorKind::RateLimited);
+    assert_eq!(Error::from_status(503, "down".to_string()).kind(), ErrorKind::Server);
+    assert_eq!(Error::from_status(418, "teapot".to_string()).kind(), ErrorKind::Api);
+    assert_eq!(Error::InvalidApiKey.kind(), ErrorKind::Authentication);
+    assert_eq!(Error::CircuitOpen.kind(), ErrorKind::NotSent);
+    let exhausted = Error::RetryBudgetExhausted {
+        attempts: 3,
+        last_error: Box::new(Error::from_status(502, "bad gateway".to_string())),
+    };
+    assert_eq!(exhausted.kind(), ErrorKind::Server);
+}
+
+#[tokio::test]
+async fn test_deadline_skips_retries_that_cannot_finish() {
+    use std::time::{Duration, Instant};
+    use zeroentropy_community::{
+        async_trait, ConstantBackoff, Error, HttpRequest, HttpResponse, HttpTransport,
+        RequestOptions,
+    };
+
+    #[derive(Clone)]
+    struct Slow(MockTransport);
+
+    #[async_trait]
+    impl HttpTransport for Slow {
+        async fn send(&self, request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            tokio::time::sleep(Duration::from_millis(60)).await;
+            self.0.send(request).await
+        }
+    }
+
+    let mock = MockTransport::with_responses(vec![(503, r#"{"message": "unavailable"}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(ConstantBackoff::new(3, Duration::from_millis(10)))
+        .transport(Slow(mock.clone()))
+        .build()
+        .unwrap();
+
+    // The retry could start in time but not finish
+    let result = client
+        .collections()
+        .with_options(RequestOptions::new().deadline(Instant::now() + Duration::from_millis(100)))
+        .get_list()
+        .await;
+    assert!(matches!(result, Err(Error::InternalServerError(_))));
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_undecodable_success_body() {
+    use zeroentropy_community::{Error, ErrorKind};
+
+    let body = r#"{"collections": "not-a-list"}"#;
+    let mock = MockTransport::with_responses(vec![(200, body)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock)
+        .build()
+        .unwrap();
+
+    let error = client.collections().get_list().await.unwrap_err();
+    match &error {
+        Error::Decode { endpoint, body: raw, .. } => {
+            assert_eq!(endpoint, "/collections/get-collection-list");
+            assert_eq!(raw, body);
+        }
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+    assert_eq!(error.kind(), ErrorKind::Serialization);
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[tokio::test]
+async fn test_refresh_api_key_on_401() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use zeroentropy_community::{async_trait, ApiKeyProvider, Error};
+
+    /// Hands out a new key generation after each refresh
+    #[derive(Clone, Default)]
+    struct Rotating {
+        generation: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl ApiKeyProvider for Rotating {
+        async fn api_key(&self) -> zeroentropy_community::Result<String> {
+            Ok(format!("key-{}", self.generation.load(Ordering::SeqCst)))
+        }
+
+        async fn refresh(&self) -> zeroentropy_community::Result<()> {
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    let mock = MockTransport::with_responses(vec![
+        (401, r#"{"message": "key expired"}"#),
+        (200, r#"{"collections": []}"#),
+        (401, r#"{"message": "key expired"}"#),
+        (401, r#"{"message": "key revoked"}"#),
+    ]);
+    let provider = Rotating::default();
+    let client = Client::builder()
+        .api_key_provider(provider.clone())
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    client.collections().get_list().await.unwrap();
+    let requests = mock.requests();
+    assert_eq!(requests[0].headers["authorization"], "Bearer key-0");
+    assert_eq!(requests[1].headers["authorization"], "Bearer key-1");
+
+    // Refreshed only once per call
+    let result = client.collections().get_list().await;
+    assert!(matches!(result, Err(Error::AuthenticationError(_))));
+    assert_eq!(mock.requests().len(), 4);
+    assert_eq!(provider.generation.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_debug_redacts_api_key() {
+    let builder = Client::builder()
+        .api_key("ze_secret_key_abcd")
+        .base_url("https://example.test/v1")
+        .default_header("X-Secret", "hunter2");
+    let debug = format!("{:?}", builder);
+    assert!(!debug.contains("ze_secret_key"));
+    assert!(!debug.contains("hunter2"));
+    assert!(debug.contains("****abcd"));
+
+    let client = builder.build().unwrap();
+    let debug = format!("{:?}", client);
+    assert!(!debug.contains("ze_secret_key"));
+    assert!(!debug.contains("hunter2"));
+    assert!(debug.contains("****abcd"));
+    assert!(debug.contains("x-secret"));
+
+    assert_eq!(client.base_url(), "https://example.test/v1");
+    assert_eq!(client.base_urls(), ["https://example.test/v1"]);
+
+    let short = Client::new("abcd").unwrap();
+    assert!(!format!("{:?}", short).contains("abcd"));
+}
+
+#[tokio::test]
+async fn test_transport_error_context() {
+    use std::error::Error as _;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use zeroentropy_community::{
+        async_trait, Error, ErrorKind, HttpRequest, HttpResponse, HttpTransport, RetryPolicy,
+    };
+
+    #[derive(Clone, Default)]
+    struct Unreachable {
+        sends: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl HttpTransport for Unreachable {
+        async fn send(&self, _request: HttpRequest) -> zeroentropy_community::Result<HttpResponse> {
+            self.sends.fetch_add(1, Ordering::SeqCst);
+            Err(Error::Transport("connection refused".into()))
+        }
+    }
+
+    struct RetryTwice;
+
+    impl RetryPolicy for RetryTwice {
+        fn should_retry(&self, attempt: u32, _error: &Error) -> Option<Duration> {
+            (attempt <= 2).then_some(Duration::ZERO)
+        }
+    }
+
+    let transport = Unreachable::default();
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_policy(RetryTwice)
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let error = client.collections().get_list().await.unwrap_err();
+    assert_eq!(transport.sends.load(Ordering::SeqCst), 3);
+    match &error {
+        Error::Request { endpoint, attempts, .. } => {
+            assert_eq!(endpoint, "/collections/get-collection-list");
+            assert_eq!(*attempts, 3);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert_eq!(error.kind(), ErrorKind::Transport);
+    assert_eq!(error.endpoint(), Some("/collections/get-collection-list"));
+    assert!(error.is_retryable());
+
+    // The chain leads down to the transport's own error
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), "Transport error: connection refused");
+    assert_eq!(source.source().unwrap().to_string(), "connection refused");
+}
+
+#[tokio::test]
+async fn test_writes_not_retried_by_default() {
+    use zeroentropy_community::{Error, Jitter};
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": []}"#),
+        (429, r#"{"message": "slow down"}"#),
+        (200, r#"{"message": "ok"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_jitter(Jitter::None)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    // The first 503 may hide an applied write
+    let result = client.documents().add_text("c", "doc.txt", "hello", None).await;
+    assert!(matches!(result, Err(Error::InternalServerError(_))));
+    assert_eq!(mock.requests().len(), 1);
+
+    // Reads are retried
+    client.collections().get_list().await.unwrap();
+    assert_eq!(mock.requests().len(), 3);
+
+    // A rate-limited write was never applied
+    tokio::time::pause();
+    client.collections().add("c").await.unwrap();
+    assert_eq!(mock.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn test_retry_delays() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use zeroentropy_community::{Error, Jitter};
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (503, r#"{"message": "unavailable"}"#),
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": []}"#),
+    ]);
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let recorded = delays.clone();
+    let client = Client::builder()
+        .api_key("test-key")
+        .max_retries(3)
+        .retry_jitter(Jitter::None)
+        .retry_base_delay(Duration::from_millis(10))
+        .retry_max_delay(Duration::from_millis(25))
+        .on_retry(move |event| recorded.lock().unwrap().push(event.delay))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    client.collections().get_list().await.unwrap();
+    assert_eq!(
+        *delays.lock().unwrap(),
+        [
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+            Duration::from_millis(25)
+        ]
+    );
+
+    let result = Client::builder()
+        .api_key("test-key")
+        .retry_base_delay(Duration::from_secs(10))
+        .retry_max_delay(Duration::from_secs(1))
+        .build();
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn test_client_stats() {
+    use zeroentropy_community::{ClientStats, Jitter};
+
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"collections": []}"#),
+        (404, r#"{"message": "no such collection"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .retry_jitter(Jitter::None)
+        .retry_base_delay(std::time::Duration::ZERO)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    assert_eq!(client.stats(), ClientStats::default());
+
+    client.collections().get_list().await.unwrap();
+    assert!(client.clone().collections().delete("missing").await.is_err());
+
+    let stats = client.stats();
+    assert_eq!(stats.requests(), 3);
+    assert_eq!(stats.requests_by_endpoint["/collections/get-collection-list"], 2);
+    assert_eq!(stats.requests_by_endpoint["/collections/delete-collection"], 1);
+    assert_eq!(stats.errors(), 2);
+    assert_eq!(stats.errors_by_status[&503], 1);
+    assert_eq!(stats.errors_by_status[&404], 1);
+    assert_eq!(stats.retries, 1);
+    assert_eq!(stats.transport_errors, 0);
+    let sent: usize = mock.requests().iter().map(|r| r.body.len()).sum();
+    assert_eq!(stats.bytes_sent, sent as u64);
+}
+
+#[tokio::test]
+async fn test_index_report() {
+    use zeroentropy_community::IndexStatus;
+
+    let statuses = ["indexed", "indexing", "parsing_failed", "not_parsed"];
+    let first_page: Vec<serde_json::Value> = (0..1024)
+        .map(|i| {
+            serde_json::json!({
+                "path": format!("doc{:04}.txt", i),
+                "index_status": statuses[i % statuses.len()],
+                "metadata": {"team": "search"},
+            })
+        })
+        .collect();
+    let mock = MockTransport::default();
+    mock.push(
+        200,
+        Default::default(),
+        serde_json::to_vec(&serde_json::json!({ "documents": first_page })).unwrap(),
+    );
+    mock.push(
+        200,
+        Default::default(),
+        br#"{"documents": [{"path": "zz.txt", "index_status": "indexed"}]}"#.to_vec(),
+    );
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let report = client.documents().index_report("docs").await.unwrap();
+    assert_eq!(report.collection_name, "docs");
+    assert_eq!(report.total(), 1025);
+    assert_eq!(report.indexed, 257);
+    assert_eq!(report.count(IndexStatus::Indexing), 256);
+    assert_eq!(report.failed(), 256);
+    assert_eq!(report.pending(), 512);
+    assert!(!report.is_settled());
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    let second: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(second["path_gt"], "doc1023.txt");
+    assert_eq!(second["limit"], 1024);
+}
+
+#[tokio::test]
+async fn test_list_models() {
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"models": [{"model_id": "zerank-1"}, {"model_id": "zerank-1-small"}]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let response = client.models().list().await.unwrap();
+    let ids: Vec<&str> = response.models.iter().map(|m| m.model_id.as_str()).collect();
+    assert_eq!(ids, ["zerank-1", "zerank-1-small"]);
+    assert!(mock.requests()[0].url.ends_with("/models/get-model-list"));
+}
+
+#[tokio::test]
+async fn test_parse_document() {
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"pages": ["Page one", "Page two"]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let parsed = client.parsers().parse_document("JVBERi0=").await.unwrap();
+    assert_eq!(parsed.pages, ["Page one", "Page two"]);
+    assert_eq!(parsed.text(), "Page one\n\nPage two");
+
+    let request = &mock.requests()[0];
+    assert!(request.url.ends_with("/parsers/parse-document"));
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body, serde_json::json!({"base64_data": "JVBERi0="}));
+}
+
+#[tokio::test]
+async fn test_admin_api_keys() {
+    let mock = MockTransport::with_responses(vec![
+        (503, r#"{"message": "unavailable"}"#),
+        (200, r#"{"api_key_id": "key_1", "api_key": "ze_live_0123456789wxyz"}"#),
+        (200, r#"{"api_keys": [{"api_key_id": "key_1", "name": "ingest-worker"}]}"#),
+        (200, r#"{"message": "revoked"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let admin = client.admin();
+
+    // Creating a key is a write and is not retried
+    assert!(admin.create_api_key("ingest-worker").await.is_err());
+    let created = admin.create_api_key("ingest-worker").await.unwrap();
+    assert_eq!(created.api_key, "ze_live_0123456789wxyz");
+    assert!(!format!("{:?}", created).contains("0123456789"));
+
+    let keys = admin.list_api_keys().await.unwrap();
+    assert_eq!(keys.api_keys[0].name, "ingest-worker");
+    admin.revoke_api_key("key_1").await.unwrap();
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(requests[3].url.ends_with("/admin/revoke-api-key"));
+    let body: serde_json::Value = serde_json::from_slice(&requests[3].body).unwrap();
+    assert_eq!(body, serde_json::json!({"api_key_id": "key_1"}));
+}
+
+#[tokio::test]
+async fn test_organization_and_project_scope() {
+    use std::time::Duration;
+    use zeroentropy_community::{Error, InMemoryCache};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .organization("org_1")
+        .project("staging")
+        .cache(InMemoryCache::new(10), Duration::from_secs(60))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let production = client.with_project("production").unwrap();
+    assert_eq!(client.project(), Some("staging"));
+    assert_eq!(production.project(), Some("production"));
+    assert_eq!(production.organization(), Some("org_1"));
+
+    for client in [&client, &production, &client] {
+        client
+            .queries()
+            .top_documents("docs", "rust", 5, None, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    // Cached per project
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].headers["x-organization-id"], "org_1");
+    assert_eq!(requests[0].headers["x-project-id"], "staging");
+    assert_eq!(requests[1].headers["x-project-id"], "production");
+
+    assert!(matches!(client.with_project("bad\nvalue"), Err(Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn test_add_batch() {
+    use zeroentropy_community::{DocumentSpec, Error, RequestOptions};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"message": "added"}"#),
+        (409, r#"{"message": "already exists"}"#),
+        (200, r#"{"message": "added"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let documents = vec![
+        DocumentSpec::text("a.txt", "first"),
+        DocumentSpec::text("b.txt", "second"),
+        DocumentSpec::text("c.txt", "third").overwrite(true),
+    ];
+    let results = client
+        .documents()
+        .with_options(RequestOptions::new().idempotency_key("import"))
+        .add_batch_with_concurrency("docs", documents, 1)
+        .await;
+
+    let paths: Vec<&str> = results.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["a.txt", "b.txt", "c.txt"]);
+    assert!(results[0].result.is_ok());
+    assert!(matches!(results[1].result, Err(Error::Conflict(_))));
+    assert!(results[2].result.is_ok());
+
+    let requests = mock.requests();
+    assert_eq!(requests[1].headers["idempotency-key"], "import-1");
+    let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+    assert_eq!(body["path"], "c.txt");
+    assert_eq!(body["overwrite"], true);
+}
+
+#[tokio::test]
+async fn test_add_file_detects_type() {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let dir = std::env::temp_dir().join(format!("ze-add-file-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("notes.md"), "# Notes\n\nSome text").unwrap();
+    // Large enough to be encoded in several chunks
+    let mut pdf = b"%PDF-1.7\n".to_vec();
+    pdf.extend((0..500_000u32).map(|i| (i % 251) as u8));
+    std::fs::write(dir.join("report.pdf"), &pdf).unwrap();
+    std::fs::write(dir.join("data.bin"), [0xff, 0xfe, 0x00, 0x80]).unwrap();
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"message": "added"}"#),
+        (200, r#"{"message": "added"}"#),
+        (200, r#"{"message": "added"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let documents = client.documents();
+    for name in ["notes.md", "report.pdf", "data.bin"] {
+        documents.add_file("docs", name, dir.join(name), None).await.unwrap();
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let bodies: Vec<serde_json::Value> = mock
+        .requests()
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(
+        bodies[0]["content"],
+        serde_json::json!({"type": "text", "text": "# Notes\n\nSome text"})
+    );
+    assert_eq!(bodies[1]["content"]["type"], "auto");
+    assert_eq!(
+        bodies[1]["content"]["base64_data"],
+        general_purpose::STANDARD.encode(&pdf)
+    );
+    assert_eq!(bodies[2]["content"]["type"], "auto");
+    assert_eq!(bodies[2]["content"]["base64_data"], "//4AgA==");
+}
+
+#[tokio::test]
+async fn test_upsert_and_add_or_update() {
+    use zeroentropy_community::RequestOptions;
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"message": "added"}"#),
+        (409, r#"{"message": "document already exists"}"#),
+        (200, r#"{"message": "updated"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let documents = client.documents();
+
+    documents.upsert_text("docs", "a.txt", "alpha", None).await.unwrap();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("lang".to_string(), MetadataValue::String("en".to_string()));
+    let response = documents
+        .with_options(RequestOptions::new().idempotency_key("ingest"))
+        .add_or_update(
+            "docs",
+            "a.txt",
+            DocumentContent::Text { text: "alpha".to_string() },
+            Some(metadata),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.message, "updated");
+
+    let requests = mock.requests();
+    let bodies: Vec<serde_json::Value> = requests
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(bodies[0]["overwrite"], true);
+    assert!(bodies[1].get("overwrite").is_none());
+    assert!(requests[2].url.ends_with("/documents/update-document"));
+    assert_eq!(requests[2].headers["idempotency-key"], "ingest-update");
+    assert_eq!(
+        bodies[2],
+        serde_json::json!({
+            "collection_name": "docs",
+            "path": "a.txt",
+            "metadata": {"lang": "en"},
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_fluent_search() {
+    use zeroentropy_community::{Filter, Reranker};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let mut filter = Filter::new();
+    filter.insert("lang".to_string(), serde_json::json!({"$eq": "en"}));
+
+    client
+        .search("docs")
+        .query("what is rust")
+        .k(5)
+        .filter(filter)
+        .include_metadata(true)
+        .reranker(Reranker::Zerank1)
+        .snippets()
+        .await
+        .unwrap();
+    client.search("docs").query("rust").offset(2).documents().await.unwrap();
+    client.queries().search("docs").query("rust").pages().await.unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].url.ends_with("/queries/top-snippets"));
+    assert!(requests[1].url.ends_with("/queries/top-documents"));
+    assert!(requests[2].url.ends_with("/queries/top-pages"));
+    let bodies: Vec<serde_json::Value> = requests
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(
+        bodies[0],
+        serde_json::json!({
+            "collection_name": "docs",
+            "query": "what is rust",
+            "k": 5,
+            "filter": {"lang": {"$eq": "en"}},
+            "include_document_metadata": true,
+            "reranker": "zerank-1",
+        })
+    );
+    assert_eq!(bodies[1]["k"], 12);
+    assert_eq!(bodies[2]["k"], 10);
+}
+
+#[tokio::test]
+async fn test_fluent_search_rejects_unused_options() {
+    use zeroentropy_community::Error;
+
+    let mock = MockTransport::with_responses(vec![]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let err = client.search("docs").documents().await.unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(_)));
+    let err = client.search("docs").query("  ").snippets().await.unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(_)));
+    let search = || client.search("docs").query("rust");
+    let err = search().precise(true).pages().await.unwrap_err();
+    assert!(err.to_string().contains("precise"));
+    let err = search().precise(true).documents().await.unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(_)));
+    let err = search().include_content(true).snippets().await.unwrap_err();
+    assert!(err.to_string().contains("include_content"));
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_default_latency_mode() {
+    use zeroentropy_community::{LatencyMode, TopDocumentsParams, TopPagesParams};
+
+    let unknown: LatencyMode = serde_json::from_str(r#""turbo""#).unwrap();
+    assert_eq!(unknown, LatencyMode::Default);
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+        (200, r#"{"results": []}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .default_latency_mode(LatencyMode::Low)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let queries = client.queries();
+
+    queries
+        .top_documents_with(TopDocumentsParams::new("docs", "rust", 5))
+        .await
+        .unwrap();
+    queries
+        .top_pages_with(TopPagesParams::new("docs", "rust", 5).latency_mode(LatencyMode::High))
+        .await
+        .unwrap();
+    queries
+        .top_pages_with(TopPagesParams::new("docs", "rust", 5).latency_mode(LatencyMode::Default))
+        .await
+        .unwrap();
+
+    let bodies: Vec<serde_json::Value> = mock
+        .requests()
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(bodies[0]["latency_mode"], "low");
+    assert_eq!(bodies[1]["latency_mode"], "high");
+    assert!(bodies[2].get("latency_mode").is_none());
+}
+
+#[test]
+fn test_reranker_model_ids() {
+    use zeroentropy_community::Reranker;
+
+    assert_eq!(Reranker::from("zerank-1-small"), Reranker::Zerank1Small);
+    assert_eq!(Reranker::Zerank2.to_string(), "zerank-2");
+    let custom: Reranker = serde_json::from_str(r#""zerank-9""#).unwrap();
+    assert_eq!(custom, Reranker::Custom("zerank-9".to_string()));
+    assert_eq!(serde_json::to_string(&custom).unwrap(), r#""zerank-9""#);
+    assert_eq!(
+        serde_json::to_value([Reranker::Zerank1, Reranker::Zerank1Small]).unwrap(),
+        serde_json::json!(["zerank-1", "zerank-1-small"])
+    );
+}
+
+#[tokio::test]
+async fn test_top_snippets_params() {
+    use zeroentropy_community::{Reranker, TopSnippetsParams};
+
+    let mock = MockTransport::with_responses(vec![(200, r#"{"results": []}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .default_metadata("tenant_id", "acme")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = TopSnippetsParams::new("docs", "what is rust", 5)
+        .precise(true)
+        .reranker(Reranker::Zerank1);
+    client.queries().top_snippets_with(params).await.unwrap();
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "collection_name": "docs",
+            "query": "what is rust",
+            "k": 5,
+            "filter": {"tenant_id": {"$eq": "acme"}},
+            "precise_responses": true,
+            "reranker": "zerank-1",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_top_documents_params() {
+    use zeroentropy_community::{LatencyMode, TopDocumentsParams};
+
+    let mock = MockTransport::with_responses(vec![(200, r#"{"results": []}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = TopDocumentsParams::new("docs", "what is rust", 3)
+        .include_metadata(true)
+        .latency_mode(LatencyMode::Low);
+    client.queries().top_documents_with(params).await.unwrap();
+
+    let request = &mock.requests()[0];
+    assert!(request.url.ends_with("/queries/top-documents"));
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "collection_name": "docs",
+            "query": "what is rust",
+            "k": 3,
+            "include_metadata": true,
+            "latency_mode": "low",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_top_pages_params() {
+    use zeroentropy_community::{LatencyMode, TopPagesParams};
+
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"results": [{"path": "a.pdf", "page_number": 2, "score": 0.9, "content": "text"}]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = TopPagesParams::new("docs", "what is rust", 1)
+        .include_content(true)
+        .latency_mode(LatencyMode::High);
+    let response = client.queries().top_pages_with(params).await.unwrap();
+    assert_eq!(response.results[0].content.as_deref(), Some("text"));
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "collection_name": "docs",
+            "query": "what is rust",
+            "k": 1,
+            "include_content": true,
+            "latency_mode": "high",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_rerank_params() {
+    use zeroentropy_community::{RerankDocument, RerankParams};
+
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"results": [{"id": "b", "score": 0.9, "index": 1}]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = RerankParams::new("systems", [("a", "Python"), ("b", "Rust")])
+        .document(RerankDocument::new("c", "Go"))
+        .model("zerank-1")
+        .top_k(1);
+    let response = client.models().rerank_with(params).await.unwrap();
+    assert_eq!(response.results[0].id, "b");
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "query": "systems",
+            "documents": [
+                {"id": "a", "text": "Python"},
+                {"id": "b", "text": "Rust"},
+                {"id": "c", "text": "Go"},
+            ],
+            "model_id": "zerank-1",
+            "top_k": 1,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_rerank_texts() {
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"results": [
+            {"id": "0", "score": 0.2, "index": 0},
+            {"id": "2", "score": 0.7, "index": 2},
+            {"id": "1", "score": 0.9, "index": 1}
+        ]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let texts = ["Python", "Rust", "Go"];
+    let response = client
+        .models()
+        .rerank_texts("systems", &texts, None, None)
+        .await
+        .unwrap();
+    let ranked: Vec<&str> = response.results.iter().map(|r| texts[r.index]).collect();
+    assert_eq!(ranked, ["Rust", "Go", "Python"]);
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(
+        body["documents"],
+        serde_json::json!([
+            {"id": "0", "text": "Python"},
+            {"id": "1", "text": "Rust"},
+            {"id": "2", "text": "Go"},
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_rerank_search_results() {
+    use zeroentropy_community::TopSnippetsResponse;
+
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"results": [
+            {"id": "1", "score": 0.95, "index": 1},
+            {"id": "0", "score": 0.4, "index": 0}
+        ]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let candidates: TopSnippetsResponse = serde_json::from_value(serde_json::json!({
+        "results": [
+            {"path": "a.txt", "content": "Python is slow", "score": 0.8, "page_number": null},
+            {"path": "b.txt", "content": "Rust is fast", "score": 0.6, "page_number": null},
+            {"path": "c.txt", "content": "Go is simple", "score": 0.5, "page_number": null}
+        ]
+    }))
+    .unwrap();
+    let reranked = client
+        .models()
+        .rerank_snippets("fast languages", candidates, None, Some(2))
+        .await
+        .unwrap();
+
+    let ranked: Vec<(&str, f64)> = reranked
+        .results
+        .iter()
+        .map(|r| (r.path.as_str(), r.score))
+        .collect();
+    assert_eq!(ranked, [("b.txt", 0.95), ("a.txt", 0.4)]);
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(body["documents"][1], serde_json::json!({"id": "1", "text": "Rust is fast"}));
+    assert_eq!(body["top_k"], 2);
+}
+
+#[tokio::test]
+async fn test_rerank_chunks() {
+    use zeroentropy_community::RerankParams;
+
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"results": [{"id": "a", "score": 0.3, "index": 0}, {"id": "b", "score": 0.8, "index": 1}]}"#,
+        ),
+        (
+            200,
+            r#"{"results": [{"id": "d", "score": 0.9, "index": 1}, {"id": "c", "score": 0.1, "index": 0}]}"#,
+        ),
+        (200, r#"{"results": [{"id": "e", "score": 0.5, "index": 0}]}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = RerankParams::new(
+        "query",
+        [("a", "A"), ("b", "B"), ("c", "C"), ("d", "D"), ("e", "E")],
+    )
+    .chunk_size(2)
+    .top_k(3);
+    let response = client.models().rerank_with(params).await.unwrap();
+
+    let ranked: Vec<(&str, usize)> = response
+        .results
+        .iter()
+        .map(|r| (r.id.as_str(), r.index))
+        .collect();
+    assert_eq!(ranked, [("d", 3), ("b", 1), ("e", 4)]);
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    for request in &requests {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["top_k"], 3);
+        assert!(body["documents"].as_array().unwrap().len() <= 2);
+        assert!(body.get("chunk_size").is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_query_offset() {
+    use zeroentropy_community::{
+        Error, ErrorKind, TopDocumentsParams, TopPagesParams, MAX_TOP_DOCUMENTS_K, MAX_TOP_PAGES_K,
+    };
+
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"results": [
+            {"path": "a.txt", "score": 0.9},
+            {"path": "b.txt", "score": 0.8},
+            {"path": "c.txt", "score": 0.7}
+        ]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = TopDocumentsParams::new("docs", "query", 2).offset(2);
+    let response = client.queries().top_documents_with(params).await.unwrap();
+    let paths: Vec<&str> = response.results.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["c.txt"]);
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(body["k"], 4);
+    assert!(body.get("offset").is_none());
+
+    // k + offset past the API's limit fails before anything is sent
+    let params = TopDocumentsParams::new("docs", "query", 2000).offset(MAX_TOP_DOCUMENTS_K);
+    let err = client.queries().top_documents_with(params).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(_)));
+    assert_eq!(err.kind(), ErrorKind::InvalidRequest);
+    let params = TopPagesParams::new("docs", "query", MAX_TOP_PAGES_K).offset(1);
+    assert!(client.queries().top_pages_with(params).await.is_err());
+    let err = client.search("docs").query("rust").k(100).offset(100).snippets().await.unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(_)));
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_top_documents_include_content() {
+    use zeroentropy_community::TopDocumentsParams;
+
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"results": [
+            {"path": "a.pdf", "score": 0.9, "content": "full text", "file_url": "https://files.example/a.pdf"},
+            {"path": "b.txt", "score": 0.8}
+        ]}"#,
+    )]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let params = TopDocumentsParams::new("docs", "query", 2).include_content(true);
+    let response = client.queries().top_documents_with(params).await.unwrap();
+    assert_eq!(response.results[0].content.as_deref(), Some("full text"));
+    assert_eq!(
+        response.results[0].file_url.as_deref(),
+        Some("https://files.example/a.pdf")
+    );
+    assert!(response.results[1].content.is_none());
+    assert!(response.results[1].file_url.is_none());
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(body["include_content"], true);
+}
+
+#[tokio::test]
+async fn test_get_raw_document() {
+    let mock = MockTransport::with_responses(vec![(
+        200,
+        r#"{"document": {"path": "a.pdf", "index_status": "indexed", "file_url": "https://files.example/a.pdf?signature=secret"}}"#,
+    )]);
+    mock.push(200, reqwest::header::HeaderMap::new(), b"%PDF-1.7".to_vec());
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let bytes = client.documents().get_raw("docs", "a.pdf").await.unwrap();
+    assert_eq!(bytes, b"%PDF-1.7");
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].url.ends_with("/documents/get-document-info"));
+    assert_eq!(requests[1].method, reqwest::Method::GET);
+    assert_eq!(requests[1].url, "https://files.example/a.pdf?signature=secret");
+    assert!(requests[1].headers.get("authorization").is_none());
+
+    let stats = client.stats();
+    assert_eq!(stats.requests_by_endpoint["https://files.example/a.pdf"], 1);
+}
+
+#[tokio::test]
+async fn test_add_url() {
+    use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+    use zeroentropy_community::{Error, ErrorKind};
+
+    let headers = |content_type: &'static str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        headers
+    };
+    let mock = MockTransport::default();
+    mock.push(200, headers("application/pdf"), b"%PDF-1.7".to_vec());
+    mock.push(200, Default::default(), br#"{"message": "added"}"#.to_vec());
+    mock.push(200, headers("text/markdown; charset=utf-8"), b"# Notes".to_vec());
+    mock.push(200, Default::default(), br#"{"message": "added"}"#.to_vec());
+    mock.push(200, headers("text/html"), b"<html></html>".to_vec());
+    mock.push(200, headers("application/pdf"), vec![0; 2048]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let documents = client.documents();
+
+    documents
+        .add_url("docs", "paper.pdf", "https://example.org/paper.pdf?token=secret", None)
+        .await
+        .unwrap();
+    documents
+        .add_url("docs", "notes.md", "https://example.org/notes.md", None)
+        .await
+        .unwrap();
+    let err = documents
+        .add_url("docs", "page.pdf", "https://example.org/page", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        Error::UnsupportedContentType { content_type, .. } if content_type == "text/html"
+    ));
+    let err = documents
+        .add_url_with_limit("docs", "big.pdf", "https://example.org/big.pdf", None, 1024)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ResourceTooLarge { limit: 1024, .. }));
+    assert_eq!(err.kind(), ErrorKind::InvalidRequest);
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 6);
+    assert_eq!(requests[0].method, reqwest::Method::GET);
+    assert!(requests[0].headers.get("authorization").is_none());
+    let bodies: Vec<serde_json::Value> = [1, 3]
+        .iter()
+        .map(|&i| serde_json::from_slice(&requests[i].body).unwrap())
+        .collect();
+    assert_eq!(
+        bodies[0]["content"],
+        serde_json::json!({"type": "auto", "base64_data": "JVBERi0xLjc="})
+    );
+    assert_eq!(bodies[1]["content"], serde_json::json!({"type": "text", "text": "# Notes"}));
+}
+
+#[tokio::test]
+async fn test_refresh_file_url() {
+    use std::time::Duration;
+    use zeroentropy_community::InMemoryCache;
+
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"document": {"path": "a.pdf", "index_status": "indexed", "file_url": "https://files.example/a.pdf?v=1", "file_url_expires_at": "2026-01-01T00:00:00Z"}}"#,
+        ),
+        (
+            200,
+            r#"{"document": {"path": "a.pdf", "index_status": "indexed", "file_url": "https://files.example/a.pdf?v=2"}}"#,
+        ),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .cache(InMemoryCache::new(10), Duration::from_secs(60))
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let info = client.documents().get_info("docs", "a.pdf", None).await.unwrap();
+    assert_eq!(info.document.file_url.as_deref(), Some("https://files.example/a.pdf?v=1"));
+    assert_eq!(
+        info.document.file_url_expires_at.as_deref(),
+        Some("2026-01-01T00:00:00Z")
+    );
+
+    // The cached response holds the old URL, so refreshing goes to the API
+    let url = client
+        .documents()
+        .refresh_file_url("docs", "a.pdf")
+        .await
+        .unwrap();
+    assert_eq!(url.as_deref(), Some("https://files.example/a.pdf?v=2"));
+    assert_eq!(mock.requests().len(), 2);
+
+    // A document added as text has no file
+    mock.push(
+        200,
+        Default::default(),
+        br#"{"document": {"path": "notes.txt", "index_status": "indexed"}}"#.to_vec(),
+    );
+    mock.push(
+        200,
+        Default::default(),
+        br#"{"document": {"path": "notes.txt", "index_status": "indexed"}}"#.to_vec(),
+    );
+    let url = client.documents().refresh_file_url("docs", "notes.txt").await.unwrap();
+    assert_eq!(url, None);
+    let err = client.documents().get_raw("docs", "notes.txt").await.unwrap_err();
+    assert!(matches!(
+        err,
+        zeroentropy_community::Error::NoStoredFile { ref path } if path == "notes.txt"
+    ));
+    assert_eq!(err.kind(), zeroentropy_community::ErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn test_document_exists() {
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"document": {"path": "a.txt", "index_status": "indexed"}}"#),
+        (404, r#"{"message": "document not found"}"#),
+        (403, r#"{"message": "forbidden"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    assert!(client.documents().exists("docs", "a.txt").await.unwrap());
+    assert!(!client.documents().exists("docs", "b.txt").await.unwrap());
+    let err = client.documents().exists("docs", "c.txt").await.unwrap_err();
+    assert_eq!(err.status_code(), Some(403));
+}
+
+#[tokio::test]
+async fn test_ensure_collection() {
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"message": "created"}"#),
+        (409, r#"{"message": "collection already exists"}"#),
+        (500, r#"{"message": "boom"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .max_retries(0)
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    assert!(client.collections().ensure("docs").await.unwrap());
+    assert!(!client.collections().ensure("docs").await.unwrap());
+    assert!(client.collections().ensure("docs").await.is_err());
+}
+
+#[tokio::test]
+async fn test_copy_collection() {
+    use zeroentropy_community::CopyOptions;
+
+    let mock = MockTransport::with_responses(vec![
+        (409, r#"{"message": "collection already exists"}"#),
+        (
+            200,
+            r#"{"documents": [
+                {"path": "a.txt", "index_status": "indexed", "metadata": {"lang": "en"}},
+                {"path": "b.txt", "index_status": "indexed"}
+            ]}"#,
+        ),
+        (404, r#"{"message": "document not found"}"#),
+        (
+            200,
+            r#"{"document": {"path": "a.txt", "index_status": "indexed", "content": {"type": "text", "text": "alpha"}}}"#,
+        ),
+        (200, r#"{"message": "added"}"#),
+        // Already in the destination, so skipped without fetching its content
+        (200, r#"{"document": {"path": "b.txt", "index_status": "indexed"}}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .default_metadata("tenant_id", "acme")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let report = client
+        .collections()
+        .copy("staging", "prod", CopyOptions::new().concurrency(1).resume_after("0.txt"))
+        .await
+        .unwrap();
+    assert_eq!(report.copied, 1);
+    assert_eq!(report.skipped, 1);
+    assert!(report.failed.is_empty());
+    assert_eq!(report.last_path.as_deref(), Some("b.txt"));
+
+    let bodies: Vec<serde_json::Value> = mock
+        .requests()
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect();
+    assert_eq!(bodies[1]["collection_name"], "staging");
+    assert_eq!(bodies[1]["path_gt"], "0.txt");
+    assert_eq!(bodies[2], serde_json::json!({"collection_name": "prod", "path": "a.txt"}));
+    // Metadata is copied as is, without the client's default metadata
+    assert_eq!(
+        bodies[4],
+        serde_json::json!({
+            "collection_name": "prod",
+            "path": "a.txt",
+            "content": {"type": "text", "text": "alpha"},
+            "metadata": {"lang": "en"},
+            "overwrite": false,
+        })
+    );
+    assert_eq!(bodies[5], serde_json::json!({"collection_name": "prod", "path": "b.txt"}));
+    assert_eq!(bodies.len(), 6);
+}
+
+#[test]
+fn test_metadata_value_types() {
+    let metadata: zeroentropy_community::Metadata = serde_json::from_value(serde_json::json!({
+        "title": "Attention",
+        "tags": ["ml", "nlp"],
+        "year": 2017,
+        "score": 4.5,
+        "peer_reviewed": true,
+    }))
+    .unwrap();
+    assert_eq!(metadata["title"], MetadataValue::String("Attention".to_string()));
+    assert_eq!(
+        metadata["tags"],
+        MetadataValue::Array(vec!["ml".to_string(), "nlp".to_string()])
+    );
+    assert_eq!(metadata["year"], MetadataValue::Integer(2017));
+    assert_eq!(metadata["score"], MetadataValue::Number(4.5));
+    assert_eq!(metadata["peer_reviewed"], MetadataValue::Bool(true));
+
+    assert_eq!(serde_json::to_value(MetadataValue::from(2017)).unwrap(), serde_json::json!(2017));
+    assert_eq!(serde_json::to_value(MetadataValue::from(0.5)).unwrap(), serde_json::json!(0.5));
+    assert_eq!(serde_json::to_value(MetadataValue::from(false)).unwrap(), serde_json::json!(false));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_datetime_metadata_and_filters() {
+    use chrono::{TimeZone, Utc};
+    use zeroentropy_community::{DateFilter, Filter};
+
+    let published = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+    let value = MetadataValue::from(published);
+    assert_eq!(serde_json::to_value(&value).unwrap(), serde_json::json!("2024-05-01T12:30:00Z"));
+
+    // Timestamps come back as strings
+    let read: MetadataValue = serde_json::from_str(r#""2024-05-01T12:30:00Z""#).unwrap();
+    assert_eq!(read, MetadataValue::String("2024-05-01T12:30:00Z".to_string()));
+    assert_eq!(read.as_datetime(), Some(published));
+    assert_eq!(MetadataValue::from(2024).as_datetime(), None);
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).unwrap();
+    assert_eq!(
+        serde_json::to_value(Filter::date_between("published", start, end)).unwrap(),
+        serde_json::json!({
+            "published": {"$gte": "2024-01-01T00:00:00Z", "$lte": "2024-12-31T23:59:59Z"}
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Filter::date_after("published", start)).unwrap(),
+        serde_json::json!({"published": {"$gt": "2024-01-01T00:00:00Z"}})
+    );
+    assert_eq!(
+        serde_json::to_value(Filter::date_before("published", end)).unwrap(),
+        serde_json::json!({"published": {"$lt": "2024-12-31T23:59:59Z"}})
+    );
+}
+
+#[tokio::test]
+async fn test_count_documents() {
+    use zeroentropy_community::{Error, ErrorKind, F};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"num_documents": 42}"#),
+        (200, r#"{"num_documents": 42}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let documents = client.documents();
+
+    assert_eq!(documents.count("docs", None).await.unwrap(), 42);
+    let requests = mock.requests();
+    assert!(requests[0].url.ends_with("/status/get-status"));
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body, serde_json::json!({"collection_name": "docs"}));
+
+    // The API cannot count by metadata
+    let err = documents
+        .count("docs", Some(F::field("lang").eq("en").build()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidParameter(_)));
+    assert_eq!(err.kind(), ErrorKind::InvalidRequest);
+    assert_eq!(mock.requests().len(), 1);
+
+    // Default metadata does not turn a count into a listing
+    let client = Client::builder()
+        .api_key("test-key")
+        .default_metadata("tenant_id", "acme")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    assert_eq!(client.documents().count("docs", None).await.unwrap(), 42);
+    assert!(mock.requests()[1].url.ends_with("/status/get-status"));
+}
+
+#[tokio::test]
+async fn test_collection_list_with_stats() {
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"collections": ["docs", "gone"]}"#),
+        (
+            200,
+            r#"{"documents": [
+                {"path": "a.txt", "index_status": "indexed"},
+                {"path": "b.txt", "index_status": "indexing"}
+            ]}"#,
+        ),
+        (404, r#"{"message": "collection not found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let stats = client.collections().get_list_with_stats().await.unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].collection_name, "docs");
+    assert_eq!(stats[0].total(), 2);
+    assert_eq!(stats[0].indexed, 1);
+    assert_eq!(stats[0].indexing, 1);
+    assert_eq!(mock.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_reindex_failed_documents() {
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"documents": [
+                {"path": "a.txt", "index_status": "indexed"},
+                {"path": "b.txt", "index_status": "parsing_failed", "metadata": {"lang": "en"}}
+            ]}"#,
+        ),
+        (
+            200,
+            r#"{"document": {"path": "b.txt", "index_status": "parsing_failed", "content": {"type": "text", "text": "beta"}}}"#,
+        ),
+        (200, r#"{"message": "added"}"#),
+        (
+            200,
+            r#"{"documents": [{"path": "c.txt", "index_status": "indexing_failed"}]}"#,
+        ),
+        (404, r#"{"message": "document not found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let report = client.documents().reindex_failed("docs").await.unwrap();
+    assert_eq!(report.resubmitted, vec!["b.txt".to_string()]);
+    assert!(report.still_failing.is_empty());
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[2].url.ends_with("/documents/add-document"));
+    let body: serde_json::Value = serde_json::from_slice(&requests[2].body).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "collection_name": "docs",
+            "path": "b.txt",
+            "content": {"type": "text", "text": "beta"},
+            "metadata": {"lang": "en"},
+            "overwrite": true,
+        })
+    );
+
+    let report = client.documents().reindex_failed("docs").await.unwrap();
+    assert!(report.resubmitted.is_empty());
+    assert_eq!(report.still_failing.len(), 1);
+    assert_eq!(report.still_failing[0].0, "c.txt");
+    assert!(report.still_failing[0].1.is_not_found());
+}
+
+#[tokio::test]
+async fn test_delete_all_documents() {
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"documents": [
+                {"path": "a.txt", "index_status": "indexed"},
+                {"path": "b.txt", "index_status": "indexed"},
+                {"path": "c.txt", "index_status": "indexed"}
+            ]}"#,
+        ),
+        (200, r#"{"message": "deleted"}"#),
+        (404, r#"{"message": "document not found"}"#),
+        (403, r#"{"message": "forbidden"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let report = client
+        .documents()
+        .delete_all_with_concurrency("docs", 1)
+        .await
+        .unwrap();
+    assert_eq!(report.deleted, 2);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "c.txt");
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(requests[3].url.ends_with("/documents/delete-document"));
+}
+
+#[tokio::test]
+async fn test_patch_metadata() {
+    use zeroentropy_community::Error;
+
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"document": {"path": "a.txt", "index_status": "indexed", "metadata": {"lang": "en"}}}"#,
+        ),
+        (200, r#"{"message": "updated"}"#),
+        (
+            200,
+            r#"{"document": {"path": "a.txt", "index_status": "indexed", "metadata": {"lang": "en"}}}"#,
+        ),
+        (
+            200,
+            r#"{"document": {"path": "a.txt", "index_status": "indexed", "metadata": {"lang": "fr"}}}"#,
+        ),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let metadata = client
+        .documents()
+        .patch_metadata("docs", "a.txt", |metadata| {
+            metadata.insert("status".to_string(), MetadataValue::String("reviewed".to_string()));
+        })
+        .await
+        .unwrap();
+    assert_eq!(metadata.len(), 2);
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[1].body).unwrap();
+    assert_eq!(
+        body["metadata"],
+        serde_json::json!({"lang": "en", "status": "reviewed"})
+    );
+
+    // Changed between the read and the write back
+    let err = client
+        .documents()
+        .patch_metadata_checked("docs", "a.txt", |metadata| {
+            metadata.remove("lang");
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ConcurrentModification { ref path } if path == "a.txt"));
+    assert_eq!(err.status_code(), None);
+    assert_eq!(mock.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_get_document_text() {
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"document": {"path": "a.txt", "index_status": "indexed", "content": {"type": "text-pages", "pages": ["one", "two"]}}}"#,
+        ),
+        (
+            200,
+            r#"{"document": {"path": "b.pdf", "index_status": "indexed", "content": {"type": "auto", "base64_data": "JVBERg=="}}}"#,
+        ),
+        (200, r#"{"page": {"path": "b.pdf", "page_number": 1, "content": "first"}}"#),
+        (200, r#"{"page": {"path": "b.pdf", "page_number": 2, "content": "second"}}"#),
+        (404, r#"{"message": "page not found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let text = client.documents().get_text("docs", "a.txt").await.unwrap();
+    assert_eq!(text, "one\n\ntwo");
+
+    let text = client.documents().get_text("docs", "b.pdf").await.unwrap();
+    assert_eq!(text, "first\n\nsecond");
+
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[3].body).unwrap();
+    assert_eq!(body["page_number"], 2);
+    assert_eq!(body["include_content"], true);
+}
+
+#[tokio::test]
+async fn test_get_pages() {
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"page": {"path": "a.pdf", "page_number": 2, "content": "two"}}"#),
+        (200, r#"{"page": {"path": "a.pdf", "page_number": 3, "content": "three"}}"#),
+        (404, r#"{"message": "page not found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let pages = client.documents().get_pages("docs", "a.pdf", 2..=4).await.unwrap();
+    let contents: Vec<&str> = pages.iter().map(|p| p.content.as_deref().unwrap()).collect();
+    assert_eq!(contents, ["two", "three"]);
+    assert_eq!(mock.requests().len(), 3);
+
+    // Overlong ranges are rejected before anything is sent
+    let err = client.documents().get_pages("docs", "a.pdf", 1..=u32::MAX).await.unwrap_err();
+    assert!(matches!(err, zeroentropy_community::Error::InvalidParameter(_)));
+    assert_eq!(mock.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_get_pages_past_end_or_missing() {
+    let mock = MockTransport::with_responses(vec![
+        (404, r#"{"message": "page not found"}"#),
+        (200, r#"{"document": {"path": "a.pdf", "index_status": "indexed"}}"#),
+        (404, r#"{"message": "page not found"}"#),
+        (404, r#"{"message": "document not found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    // A window wholly past the end of an existing document is empty
+    let pages = client.documents().get_pages("docs", "a.pdf", 8..=12).await.unwrap();
+    assert!(pages.is_empty());
+
+    let err = client.documents().get_pages("docs", "typo.pdf", 1..=3).await.unwrap_err();
+    assert!(err.is_not_found());
+    let urls: Vec<_> = mock.requests().into_iter().map(|r| r.url).collect();
+    assert!(urls[1].ends_with("/documents/get-document-info"));
+    assert_eq!(urls.len(), 4);
+}
+
+#[tokio::test]
+async fn test_pages_stream() {
+    use futures_util::StreamExt;
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"page": {"path": "a.pdf", "page_number": 1, "content": "one"}}"#),
+        (200, r#"{"page": {"path": "a.pdf", "page_number": 2, "content": "two"}}"#),
+        (404, r#"{"message": "page not found"}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+
+    let mut pages = std::pin::pin!(client.documents().pages_stream("docs", "a.pdf"));
+    let first = pages.next().await.unwrap().unwrap();
+    assert_eq!(first.content.as_deref(), Some("one"));
+    // Pages are only fetched when asked for
+    assert_eq!(mock.requests().len(), 1);
+
+    let second = pages.next().await.unwrap().unwrap();
+    assert_eq!(second.page_number, 2);
+    assert!(pages.next().await.is_none());
+    assert_eq!(mock.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_wait_until_indexed() {
+    use std::time::Duration;
+    use zeroentropy_community::{Error, ErrorKind, IndexStatus};
+
+    let mock = MockTransport::with_responses(vec![
+        (200, r#"{"document": {"path": "a.txt", "index_status": "parsing"}}"#),
+        (200, r#"{"document": {"path": "a.txt", "index_status": "indexing"}}"#),
+        (200, r#"{"document": {"path": "a.txt", "index_status": "indexed"}}"#),
+        (
+            200,
+            r#"{"document": {"path": "b.pdf", "index_status": "parsing_failed", "failure_reason": "encrypted PDF"}}"#,
+        ),
+        (200, r#"{"document": {"path": "c.txt", "index_status": "not_parsed"}}"#),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let documents = client.documents();
+    let poll = Duration::from_millis(1);
+
+    let info = documents
+        .wait_until_indexed("docs", "a.txt", Duration::from_secs(5), poll)
+        .await
+        .unwrap();
+    assert_eq!(info.index_status, IndexStatus::Indexed);
+    assert_eq!(mock.requests().len(), 3);
+
+    let err = documents
+        .wait_until_indexed("docs", "b.pdf", Duration::from_secs(5), poll)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IndexingFailed);
+    assert!(matches!(
+        &err,
+        Error::IndexingFailed { status: IndexStatus::ParsingFailed, reason: Some(reason), .. }
+            if reason == "encrypted PDF"
+    ));
+
+    let err = documents
+        .wait_until_indexed("docs", "c.txt", Duration::ZERO, poll)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::WaitTimeout(_)));
+}
+
+#[tokio::test]
+async fn test_wait_until_collection_indexed() {
+    use std::time::Duration;
+    use zeroentropy_community::{Error, ErrorKind};
+
+    let mock = MockTransport::with_responses(vec![
+        (
+            200,
+            r#"{"documents": [{"path": "a.txt", "index_status": "indexed"}, {"path": "b.txt", "index_status": "indexing"}]}"#,
+        ),
+        (
+            200,
+            r#"{"documents": [{"path": "a.txt", "index_status": "indexed"}, {"path": "b.txt", "index_status": "indexing_failed"}]}"#,
+        ),
+        (
+            200,
+            r#"{"documents": [{"path": "c.txt", "index_status": "parsing"}]}"#,
+        ),
+    ]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    let collections = client.collections();
+
+    let mut progress = Vec::new();
+    let report = collections
+        .wait_until_indexed_with_progress("docs", Duration::from_secs(60), |report| {
+            progress.push((report.indexed, report.total()));
+        })
+        .await
+        .unwrap();
+    assert_eq!(progress, vec![(1, 2), (1, 2)]);
+    assert!(report.is_settled());
+    assert_eq!(report.failed(), 1);
+    assert_eq!(mock.requests().len(), 2);
+
+    let err = collections
+        .wait_until_indexed("docs", Duration::ZERO)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::WaitTimeout(_)));
+    assert_eq!(err.kind(), ErrorKind::DeadlineExceeded);
+}
+
+#[tokio::test]
+async fn test_filter_builder() {
+    use zeroentropy_community::{Filter, F};
+
+    let filter: Filter = F::field("specialty")
+        .eq("Cardiology")
+        .and(F::field("year").gte(2020))
+        .and(F::field("status").is_in(["open", "review"]))
+        .into();
+    assert_eq!(
+        serde_json::to_value(&filter).unwrap(),
+        serde_json::json!({
+            "$and": [
+                {"specialty": {"$eq": "Cardiology"}},
+                {"year": {"$gte": 2020}},
+                {"status": {"$in": ["open", "review"]}},
+            ]
+        })
+    );
+
+    let either = F::field("lang").ne("de").or(F::field("pages").lt(3.5)).build();
+    assert_eq!(
+        serde_json::to_value(&either).unwrap(),
+        serde_json::json!({
+            "$or": [{"lang": {"$ne": "de"}}, {"pages": {"$lt": 3.5}}]
+        })
+    );
+
+    let mock = MockTransport::with_responses(vec![(200, r#"{"results": []}"#)]);
+    let client = Client::builder()
+        .api_key("test-key")
+        .transport(mock.clone())
+        .build()
+        .unwrap();
+    client
+        .search("docs")
+        .query("rust")
+        .filter(F::field("tag").not_in(["draft"]))
+        .snippets()
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&mock.requests()[0].body).unwrap();
+    assert_eq!(body["filter"], serde_json::json!({"tag": {"$nin": ["draft"]}}));
+}